@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+/// Word size used to score complexity; DUST also counts triplets.
+pub const COMPLEXITY_K: usize = 3;
+
+/// Shannon entropy of the [`COMPLEXITY_K`]-mers of `seq`, scaled to 0..=1.
+///
+/// 0 means a single repeated word, as in a homopolymer; 1 means every word is
+/// as evenly spread as the number of words allows. Sequences with fewer than
+/// two words cannot be judged and score 1. Case is ignored.
+pub fn complexity(seq: &str) -> f64 {
+    let seq = seq.as_bytes();
+    if seq.len() <= COMPLEXITY_K {
+        return 1.0;
+    }
+
+    let mut counts: HashMap<Vec<u8>, usize> = HashMap::new();
+    for word in seq.windows(COMPLEXITY_K) {
+        *counts.entry(word.to_ascii_uppercase()).or_insert(0) += 1;
+    }
+
+    let words = (seq.len() - COMPLEXITY_K + 1) as f64;
+    let entropy: f64 = counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / words;
+            -p * p.log2()
+        })
+        .sum();
+
+    // The most entropy possible is every word distinct, capped by the 4^k alphabet //
+    let max_entropy = words.min(4f64.powi(COMPLEXITY_K as i32)).log2();
+    entropy / max_entropy
+}
+
+/// Start offsets of every `window`-base window of `seq` scoring below
+/// `threshold` by [`complexity`]; a sequence shorter than `window` is scored
+/// as one window.
+fn low_complexity_windows(seq: &str, window: usize, threshold: f64) -> Vec<usize> {
+    let window = window.min(seq.len());
+
+    (0..=seq.len() - window)
+        .filter(|&start| complexity(&seq[start..start + window]) < threshold)
+        .collect()
+}
+
+/// Whether any `window`-base window of `seq` scores below `threshold`.
+///
+/// See [`mask_low_complexity`] for how windows are scored. The sequence must
+/// be ASCII, as for [`crate::reads::break_into_reads`].
+pub fn is_low_complexity(seq: &str, window: usize, threshold: f64) -> bool {
+    !low_complexity_windows(seq, window, threshold).is_empty()
+}
+
+/// Replace every base in a low-complexity window of `seq` with `N`.
+///
+/// Slides a `window`-base window along `seq` one base at a time and masks it
+/// when its [`complexity`] is below `threshold`; a sequence shorter than
+/// `window` is scored as one window. The length never changes, so quality
+/// strings stay aligned. The sequence must be ASCII, as for
+/// [`crate::reads::break_into_reads`].
+pub fn mask_low_complexity(seq: &str, window: usize, threshold: f64) -> String {
+    let mut masked = vec![false; seq.len()];
+    let window = window.min(seq.len());
+
+    for start in low_complexity_windows(seq, window, threshold) {
+        masked[start..start + window].fill(true);
+    }
+
+    seq.chars()
+        .zip(masked)
+        .map(|(base, masked)| if masked { 'N' } else { base })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIXED: &str = "ACGTTGCAGGCTAGCATCGATTGACGATCG";
+
+    #[test]
+    fn homopolymer_scores_zero_and_mixed_sequence_scores_high() {
+        assert_eq!(complexity(&"A".repeat(20)), 0.0);
+        assert!(complexity(MIXED) > 0.85);
+        // Too short to judge //
+        assert_eq!(complexity("AAA"), 1.0);
+    }
+
+    #[test]
+    fn homopolymer_is_flagged_and_mixed_sequence_is_not() {
+        assert!(is_low_complexity(&"A".repeat(30), 12, 0.5));
+        assert!(!is_low_complexity(MIXED, 12, 0.5));
+        assert!(is_low_complexity(
+            &format!("{}{}", MIXED, "a".repeat(12)),
+            12,
+            0.5
+        ));
+    }
+
+    #[test]
+    fn masks_the_run_and_the_windows_straddling_it() {
+        let seq = format!("{}{}{}", &MIXED[..12], "A".repeat(12), &MIXED[12..24]);
+        let masked = mask_low_complexity(&seq, 12, 0.5);
+
+        // Windows straddling the run also score below the threshold //
+        assert_eq!(
+            masked,
+            format!("{}{}{}", &seq[..9], "N".repeat(19), &seq[28..])
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt;
+
+/// Base counts of a sequence, with case ignored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Composition {
+    pub a: usize,
+    pub c: usize,
+    pub g: usize,
+    pub t: usize,
+    pub n: usize,
+    /// Any character that is not A, C, G, T or N.
+    pub other: usize,
+}
+
+impl Composition {
+    /// Total number of characters counted.
+    pub fn total(&self) -> usize {
+        self.a + self.c + self.g + self.t + self.n + self.other
+    }
+
+    /// `count` as a fraction of [`Composition::total`], or 0 for an empty
+    /// sequence.
+    pub fn fraction(&self, count: usize) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => count as f64 / total as f64,
+        }
+    }
+
+    /// Fraction of all characters that are G or C, or 0 for an empty sequence.
+    pub fn gc_content(&self) -> f64 {
+        self.fraction(self.g + self.c)
+    }
+
+    /// [`Composition::gc_content`] as a percentage.
+    pub fn gc_percent(&self) -> f64 {
+        100.0 * self.gc_content()
+    }
+}
+
+impl fmt::Display for Composition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Length: {}", self.total())?;
+        for (name, count) in [
+            ("A", self.a),
+            ("C", self.c),
+            ("G", self.g),
+            ("T", self.t),
+            ("N", self.n),
+            ("Other", self.other),
+        ] {
+            writeln!(
+                f,
+                "{}: {} ({:.2}%)",
+                name,
+                count,
+                100.0 * self.fraction(count)
+            )?;
+        }
+        write!(f, "GC content: {:.2}%", self.gc_percent())
+    }
+}
+
+/// Tally the bases of `seq` case-insensitively.
+///
+/// Anything that is not A, C, G, T or N is counted under `other`.
+pub fn base_composition(seq: &str) -> Composition {
+    let mut composition = Composition::default();
+
+    for c in seq.chars() {
+        match c.to_ascii_uppercase() {
+            'A' => composition.a += 1,
+            'C' => composition.c += 1,
+            'G' => composition.g += 1,
+            'T' => composition.t += 1,
+            'N' => composition.n += 1,
+            _ => composition.other += 1,
+        }
+    }
+
+    composition
+}
+
+/// Fraction of G and C in `seq`, ignoring case; 0 for an empty sequence.
+pub fn gc_content(seq: &str) -> f64 {
+    base_composition(seq).gc_content()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_each_base_ignoring_case() {
+        let c = base_composition("AacGTtnX");
+
+        assert_eq!((c.a, c.c, c.g, c.t, c.n, c.other), (2, 1, 1, 2, 1, 1));
+        assert_eq!(c.total(), 8);
+    }
+
+    #[test]
+    fn fractions_are_exact() {
+        let c = base_composition("AACG");
+
+        assert_eq!(c.fraction(c.a), 0.5);
+        assert_eq!(c.fraction(c.c), 0.25);
+        assert_eq!(c.fraction(c.t), 0.0);
+        assert_eq!(c.gc_content(), 0.5);
+        assert_eq!(c.gc_percent(), 50.0);
+        assert_eq!(gc_content("GGGC"), 1.0);
+    }
+
+    #[test]
+    fn empty_sequence_has_zero_fractions() {
+        let c = base_composition("");
+
+        assert_eq!(c.total(), 0);
+        assert_eq!(c.fraction(c.a), 0.0);
+        assert_eq!(c.gc_content(), 0.0);
+        assert!(!c.to_string().contains("NaN"));
+    }
+}
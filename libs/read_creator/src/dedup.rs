@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+use crate::genome::reverse_complement;
+use crate::reads::drop_flagged;
+
+/// Flag the reads that add no new sequence, keeping the first copy of each.
+///
+/// A read is redundant when an identical read appears earlier, or, with
+/// `include_contained` set, when it is a substring of a different, longer
+/// read anywhere in the set. The containment check compares every pair of
+/// reads, so it is quadratic in the number of reads.
+pub fn redundant_reads(reads: &[String], include_contained: bool) -> Vec<bool> {
+    let mut seen = HashSet::new();
+    let mut redundant: Vec<bool> = reads
+        .iter()
+        .map(|read| !seen.insert(read.as_str()))
+        .collect();
+
+    if include_contained {
+        for (i, read) in reads.iter().enumerate() {
+            if !redundant[i] {
+                redundant[i] = reads
+                    .iter()
+                    .any(|other| other.len() > read.len() && other.contains(read.as_str()));
+            }
+        }
+    }
+
+    redundant
+}
+
+/// Flag the reads that repeat an earlier read on either strand, i.e. are
+/// identical to it or to its reverse complement.
+///
+/// Reads are compared by whichever of themselves and their
+/// [`reverse_complement`] sorts first, so the first occurrence of each
+/// sequence is kept.
+pub fn strand_redundant_reads(reads: &[String]) -> Vec<bool> {
+    let mut seen = HashSet::new();
+
+    reads
+        .iter()
+        .map(|read| {
+            let rc = reverse_complement(read);
+            let key = if rc < *read { rc } else { read.clone() };
+            !seen.insert(key)
+        })
+        .collect()
+}
+
+/// Remove exact duplicate reads, and with `both_strands` set, reads that are
+/// the reverse complement of an earlier one.
+///
+/// The first occurrence of each read is kept and input order is preserved.
+/// Returns the unique reads and how many were removed.
+pub fn deduplicate_reads(reads: Vec<String>, both_strands: bool) -> (Vec<String>, usize) {
+    let redundant = if both_strands {
+        strand_redundant_reads(&reads)
+    } else {
+        redundant_reads(&reads, false)
+    };
+    let removed = redundant.iter().filter(|&&r| r).count();
+
+    (drop_flagged(reads, &redundant), removed)
+}
+
+/// Drop redundant reads in place, keeping the first copy of each and the
+/// order of what remains. See [`redundant_reads`] for `include_contained`.
+pub fn dedup_reads(reads: &mut Vec<String>, include_contained: bool) {
+    let redundant = redundant_reads(reads, include_contained);
+    *reads = drop_flagged(std::mem::take(reads), &redundant);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(reads: &[&str]) -> Vec<String> {
+        reads.iter().map(|read| read.to_string()).collect()
+    }
+
+    #[test]
+    fn keeps_first_copy_of_duplicates_in_order() {
+        let mut reads = strings(&["ACGT", "GGCC", "ACGT", "TTAA", "GGCC"]);
+        dedup_reads(&mut reads, false);
+
+        assert_eq!(reads, strings(&["ACGT", "GGCC", "TTAA"]));
+    }
+
+    #[test]
+    fn contained_reads_are_removed_only_on_request() {
+        let reads = strings(&["CGT", "ACGTA", "TTT"]);
+
+        assert_eq!(redundant_reads(&reads, false), vec![false, false, false]);
+        assert_eq!(redundant_reads(&reads, true), vec![true, false, false]);
+    }
+
+    #[test]
+    fn removes_duplicates_and_reverse_complement_duplicates() {
+        // AACG and CGTT are reverse complements; GGCC is its own //
+        let reads = strings(&["AACG", "GGCC", "AACG", "CGTT", "GGCC", "TTGA"]);
+
+        let (unique, removed) = deduplicate_reads(reads.clone(), false);
+        assert_eq!(unique, strings(&["AACG", "GGCC", "CGTT", "TTGA"]));
+        assert_eq!(removed, 2);
+
+        let (unique, removed) = deduplicate_reads(reads, true);
+        assert_eq!(unique, strings(&["AACG", "GGCC", "TTGA"]));
+        assert_eq!(removed, 3);
+    }
+}
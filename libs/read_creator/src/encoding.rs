@@ -0,0 +1,197 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::reads::break_into_reads;
+
+/// Bases packed into each `u64` word.
+pub const BASES_PER_WORD: usize = 32;
+
+/// Why a sequence could not be packed into two bits per base.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+    /// A character other than A, C, G or T (either case) at this byte offset.
+    /// Every earlier character is an ASCII base, so it is also the index of
+    /// the offending character.
+    InvalidBase { position: usize, base: char },
+    /// A k-mer longer than [`BASES_PER_WORD`] bytes does not fit in one word.
+    KmerTooLong { len: usize },
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::InvalidBase { position, base } => {
+                write!(f, "cannot encode {:?} at position {}", base, position)
+            }
+            EncodeError::KmerTooLong { len } => {
+                write!(
+                    f,
+                    "k-mer of length {} does not fit in {} bases",
+                    len, BASES_PER_WORD
+                )
+            }
+        }
+    }
+}
+
+impl Error for EncodeError {}
+
+fn base_to_bits(position: usize, base: char) -> Result<u64, EncodeError> {
+    match base.to_ascii_uppercase() {
+        'A' => Ok(0b00),
+        'C' => Ok(0b01),
+        'G' => Ok(0b10),
+        'T' => Ok(0b11),
+        _ => Err(EncodeError::InvalidBase { position, base }),
+    }
+}
+
+fn bits_to_base(bits: u64) -> char {
+    match bits & 0b11 {
+        0b00 => 'A',
+        0b01 => 'C',
+        0b10 => 'G',
+        _ => 'T',
+    }
+}
+
+/// Pack `seq` at two bits per base (A=00, C=01, G=10, T=11).
+///
+/// Bases fill each word from the most significant bits down, 32 to a word,
+/// and the unused low bits of the last word are zero. The length is not
+/// stored, so keep it alongside the words for [`decode_2bit`].
+pub fn encode_2bit(seq: &str) -> Result<Vec<u64>, EncodeError> {
+    let mut words = vec![0u64; seq.len().div_ceil(BASES_PER_WORD)];
+
+    for (i, base) in seq.char_indices() {
+        let shift = 62 - 2 * (i % BASES_PER_WORD);
+        words[i / BASES_PER_WORD] |= base_to_bits(i, base)? << shift;
+    }
+
+    Ok(words)
+}
+
+/// Unpack the first `len` bases from words produced by [`encode_2bit`].
+///
+/// Panics if `bits` holds fewer than `len` bases.
+pub fn decode_2bit(bits: &[u64], len: usize) -> String {
+    (0..len)
+        .map(|i| bits_to_base(bits[i / BASES_PER_WORD] >> (62 - 2 * (i % BASES_PER_WORD))))
+        .collect()
+}
+
+/// Pack a k-mer of at most 32 bases into the low bits of a single word.
+///
+/// Unlike [`encode_2bit`] the bases are right-aligned, so equal k-mers of the
+/// same length always give equal keys.
+pub fn encode_kmer(kmer: &str) -> Result<u64, EncodeError> {
+    if kmer.len() > BASES_PER_WORD {
+        return Err(EncodeError::KmerTooLong { len: kmer.len() });
+    }
+
+    kmer.char_indices().try_fold(0u64, |word, (i, base)| {
+        Ok((word << 2) | base_to_bits(i, base)?)
+    })
+}
+
+/// Every sliding window of `k` bases in `genome`, packed with [`encode_kmer`].
+///
+/// The whole genome is checked first, so an invalid base is reported at its
+/// offset in `genome` and non-ASCII input never reaches the byte slicing.
+pub fn break_into_packed_kmers(genome: &str, k: usize) -> Result<Vec<u64>, EncodeError> {
+    for (i, base) in genome.char_indices() {
+        base_to_bits(i, base)?;
+    }
+
+    break_into_reads(genome, k, 1)
+        .iter()
+        .map(|kmer| encode_kmer(kmer))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A deterministic ACGT sequence of `len` bases //
+    fn sequence(len: usize) -> String {
+        (0..len)
+            .map(|i| ['A', 'C', 'G', 'T'][(i * 7 + i / 3) % 4])
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_lengths_around_word_boundaries() {
+        for len in [0, 1, 5, 31, 32, 33, 63, 64, 65, 100] {
+            let seq = sequence(len);
+            let words = encode_2bit(&seq).unwrap();
+
+            assert_eq!(words.len(), len.div_ceil(BASES_PER_WORD), "len {}", len);
+            assert_eq!(decode_2bit(&words, len), seq, "len {}", len);
+        }
+    }
+
+    #[test]
+    fn packs_from_the_most_significant_bits() {
+        assert_eq!(encode_2bit("CA").unwrap(), vec![0b01 << 62]);
+        assert_eq!(decode_2bit(&encode_2bit("acgt").unwrap(), 4), "ACGT");
+    }
+
+    #[test]
+    fn reports_the_offset_of_an_invalid_base() {
+        assert_eq!(
+            encode_2bit("ACNT"),
+            Err(EncodeError::InvalidBase {
+                position: 2,
+                base: 'N'
+            })
+        );
+        assert_eq!(
+            encode_kmer("Aé"),
+            Err(EncodeError::InvalidBase {
+                position: 1,
+                base: 'é'
+            })
+        );
+    }
+
+    #[test]
+    fn kmers_are_right_aligned_and_limited_to_one_word() {
+        assert_eq!(encode_kmer("ACGT").unwrap(), 0b00_01_10_11);
+        assert_eq!(encode_kmer("acgt"), encode_kmer("ACGT"));
+        assert!(encode_kmer(&sequence(32)).is_ok());
+        assert_eq!(
+            encode_kmer(&sequence(33)),
+            Err(EncodeError::KmerTooLong { len: 33 })
+        );
+    }
+
+    #[test]
+    fn packs_every_sliding_kmer() {
+        let packed = break_into_packed_kmers("ACGTA", 3).unwrap();
+        let expected: Vec<u64> = ["ACG", "CGT", "GTA"]
+            .iter()
+            .map(|kmer| encode_kmer(kmer).unwrap())
+            .collect();
+
+        assert_eq!(packed, expected);
+    }
+
+    #[test]
+    fn packed_kmers_report_invalid_bases_at_their_genome_offset() {
+        assert_eq!(
+            break_into_packed_kmers("AéGT", 2),
+            Err(EncodeError::InvalidBase {
+                position: 1,
+                base: 'é'
+            })
+        );
+        assert_eq!(
+            break_into_packed_kmers("ACGTNA", 2),
+            Err(EncodeError::InvalidBase {
+                position: 4,
+                base: 'N'
+            })
+        );
+    }
+}
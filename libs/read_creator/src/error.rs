@@ -0,0 +1,94 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Everything that can go wrong turning a genome file into reads.
+#[derive(Debug)]
+pub enum AssemblyError {
+    /// Reading or writing `path` failed.
+    Io { path: String, source: io::Error },
+    /// The sequence holds a non-nucleotide character at this offset.
+    InvalidAlphabet { position: usize, base: char },
+    /// The input contained no sequence at all.
+    EmptyInput,
+    /// A command-line argument is missing, malformed or conflicts with another.
+    Usage(String),
+    /// The input is not well-formed FASTA, FASTQ or sequence text, or lacks
+    /// something the chosen options need.
+    Format(String),
+}
+
+impl AssemblyError {
+    /// Adapter for `map_err` that tags an I/O error with the file involved.
+    pub(crate) fn io(path: &str) -> impl FnOnce(io::Error) -> AssemblyError + '_ {
+        move |source| AssemblyError::Io {
+            path: path.to_string(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for AssemblyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssemblyError::Io { path, source } => write!(f, "{}: {}", path, source),
+            AssemblyError::InvalidAlphabet { position, base } => {
+                write!(f, "invalid character {:?} at position {}", base, position)
+            }
+            AssemblyError::EmptyInput => write!(f, "input contains no sequence"),
+            AssemblyError::Usage(message) | AssemblyError::Format(message) => {
+                write!(f, "{}", message)
+            }
+        }
+    }
+}
+
+impl Error for AssemblyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AssemblyError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn io_errors_name_the_path_and_keep_their_source() {
+        let error =
+            AssemblyError::io("genome.fa")(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+
+        assert_eq!(error.to_string(), "genome.fa: no such file");
+        assert_eq!(error.source().unwrap().to_string(), "no such file");
+    }
+
+    #[test]
+    fn other_errors_display_without_a_source() {
+        let errors = [
+            (
+                AssemblyError::InvalidAlphabet {
+                    position: 4,
+                    base: 'X',
+                },
+                "invalid character 'X' at position 4",
+            ),
+            (AssemblyError::EmptyInput, "input contains no sequence"),
+            (
+                AssemblyError::Usage("bad read length".to_string()),
+                "bad read length",
+            ),
+            (
+                AssemblyError::Format("truncated record".to_string()),
+                "truncated record",
+            ),
+        ];
+
+        for (error, message) in errors {
+            assert_eq!(error.to_string(), message);
+            assert!(error.source().is_none());
+        }
+    }
+}
@@ -0,0 +1,507 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::error::AssemblyError;
+
+/// Whether `c` is whitespace that may break up sequence text: ASCII
+/// whitespace or a vertical tab.
+///
+/// Every parser here and [`crate::io::stream_reads`] strip exactly these, so
+/// they agree on what is a base. Other Unicode spaces are invalid bases.
+pub fn is_sequence_whitespace(c: char) -> bool {
+    c.is_ascii_whitespace() || c == '\x0B'
+}
+
+/// Drop line breaks (including `\r\n`) and any other whitespace from the genome.
+pub fn normalize_genome(raw: &str) -> String {
+    raw.chars()
+        .filter(|&c| !is_sequence_whitespace(c))
+        .collect()
+}
+
+/// Turn a raw genome file, FASTA or not, into one uppercase sequence.
+///
+/// Lines starting with `>` are dropped, so the records of a multi-FASTA file
+/// are concatenated, and all whitespace including `\r\n` is removed.
+pub fn sanitize_genome(raw: &str) -> String {
+    raw.lines()
+        .filter(|line| !line.starts_with('>'))
+        .flat_map(|line| line.chars())
+        .filter(|&c| !is_sequence_whitespace(c))
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Plain DNA: A, C, G, T and N.
+pub const DNA_ALPHABET: &[u8] = b"ACGTN";
+
+/// DNA plus the IUPAC ambiguity codes R, Y, S, W, K, M, B, D, H and V.
+pub const IUPAC_ALPHABET: &[u8] = b"ACGTRYSWKMBDHVN";
+
+/// The first byte of a sequence that is not in the expected alphabet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Byte offset of the offending byte.
+    pub position: usize,
+    pub byte: u8,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.byte.is_ascii() {
+            write!(
+                f,
+                "invalid character {:?} at position {}",
+                self.byte as char, self.position
+            )
+        } else {
+            write!(
+                f,
+                "invalid byte 0x{:02X} at position {}",
+                self.byte, self.position
+            )
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// Check that every byte of `seq` is in `alphabet`, ignoring case.
+///
+/// `alphabet` is given in uppercase, e.g. [`DNA_ALPHABET`] or
+/// [`IUPAC_ALPHABET`]. Reports the first invalid byte and its offset. Once
+/// this passes the sequence is pure ASCII, so byte slicing is safe.
+pub fn validate_sequence(seq: &str, alphabet: &[u8]) -> Result<(), ValidationError> {
+    match seq
+        .bytes()
+        .position(|byte| !alphabet.contains(&byte.to_ascii_uppercase()))
+    {
+        Some(position) => Err(ValidationError {
+            position,
+            byte: seq.as_bytes()[position],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Check that `seq` only contains nucleotides (A, C, G, T or N, either case).
+///
+/// Returns the first offending character as the error; see
+/// [`validate_sequence`] for the offset and other alphabets.
+pub fn validate_alphabet(seq: &str) -> Result<(), char> {
+    validate_sequence(seq, DNA_ALPHABET).map_err(|e| first_char_at(seq, e.position))
+}
+
+// Every byte before a validation failure is ASCII, so `position` is a char boundary //
+fn first_char_at(seq: &str, position: usize) -> char {
+    seq[position..]
+        .chars()
+        .next()
+        .unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+/// Sanitize a raw genome and check it is a non-empty nucleotide sequence.
+///
+/// Shorthand for [`prepare_genome_with`] and [`DNA_ALPHABET`].
+pub fn prepare_genome(raw: &str) -> Result<String, AssemblyError> {
+    prepare_genome_with(raw, DNA_ALPHABET)
+}
+
+/// Sanitize a raw genome and check it is a non-empty sequence over `alphabet`.
+///
+/// Runs [`sanitize_genome`] then [`validate_sequence`]; the returned sequence
+/// is uppercase ASCII and safe to slice by byte offset.
+pub fn prepare_genome_with(raw: &str, alphabet: &[u8]) -> Result<String, AssemblyError> {
+    let genome = sanitize_genome(raw);
+
+    if genome.is_empty() {
+        return Err(AssemblyError::EmptyInput);
+    }
+
+    if let Err(e) = validate_sequence(&genome, alphabet) {
+        return Err(AssemblyError::InvalidAlphabet {
+            position: e.position,
+            base: first_char_at(&genome, e.position),
+        });
+    }
+
+    Ok(genome)
+}
+
+/// A single FASTA record: the header id and its sequence lines joined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastaRecord {
+    pub id: String,
+    pub seq: String,
+}
+
+/// Parse FASTA text into its records.
+///
+/// The id is the first word of each `>` header line. Sequence lines are
+/// joined with all whitespace removed, so header text never ends up in a
+/// sequence. Lines before the first header are ignored.
+pub fn parse_fasta(raw: &str) -> Vec<FastaRecord> {
+    let mut records: Vec<FastaRecord> = Vec::new();
+
+    for line in raw.lines() {
+        if let Some(header) = line.strip_prefix('>') {
+            let id = header.split_whitespace().next().unwrap_or("").to_string();
+            records.push(FastaRecord {
+                id,
+                seq: String::new(),
+            });
+        } else if let Some(record) = records.last_mut() {
+            record.seq.push_str(&normalize_genome(line));
+        }
+    }
+
+    records
+}
+
+/// Complement of one IUPAC nucleotide code, keeping its case.
+///
+/// A/T and C/G swap, as do the two-base codes R/Y and K/M and the three-base
+/// codes B/V and D/H; S, W and N are their own complements. Any other
+/// character becomes `N` (or `n` if lowercase).
+pub fn complement(base: char) -> char {
+    let upper = match base.to_ascii_uppercase() {
+        'A' => 'T',
+        'T' => 'A',
+        'C' => 'G',
+        'G' => 'C',
+        'R' => 'Y',
+        'Y' => 'R',
+        'K' => 'M',
+        'M' => 'K',
+        'B' => 'V',
+        'V' => 'B',
+        'D' => 'H',
+        'H' => 'D',
+        'S' => 'S',
+        'W' => 'W',
+        _ => 'N',
+    };
+
+    if base.is_ascii_lowercase() {
+        upper.to_ascii_lowercase()
+    } else {
+        upper
+    }
+}
+
+/// Reverse complement of `seq`, complementing each base with [`complement`].
+///
+/// IUPAC ambiguity codes are complemented and case is kept; unknown
+/// characters come out as `N`.
+pub fn reverse_complement(seq: &str) -> String {
+    seq.chars().rev().map(complement).collect()
+}
+
+/// A single FASTQ record with its Phred+33 quality line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastqRecord {
+    pub id: String,
+    pub seq: String,
+    pub qual: String,
+}
+
+/// Parse four-line FASTQ text into its records.
+///
+/// The id is the first word of each `@` header line. Blank lines between
+/// records are skipped, while the sequence and quality lines of an empty
+/// record are kept. Fails with [`AssemblyError::Format`] when a record is
+/// truncated, its header or separator line is malformed, or its quality line
+/// is not the same length as its sequence.
+pub fn parse_fastq(raw: &str) -> Result<Vec<FastqRecord>, AssemblyError> {
+    let mut records = Vec::new();
+    let mut lines = raw
+        .lines()
+        .map(|line| line.trim_end_matches(is_sequence_whitespace));
+
+    while let Some(header) = lines.next() {
+        // Blank lines may separate records, but every line inside one counts //
+        if header.is_empty() {
+            continue;
+        }
+        let number = records.len() + 1;
+        let malformed =
+            |what: &str| AssemblyError::Format(format!("FASTQ record {}: {}", number, what));
+
+        let id = header
+            .strip_prefix('@')
+            .ok_or_else(|| malformed("header does not start with '@'"))?
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .to_string();
+        let seq = lines
+            .next()
+            .ok_or_else(|| malformed("missing sequence line"))?;
+        let separator = lines.next().ok_or_else(|| malformed("missing '+' line"))?;
+        let qual = lines
+            .next()
+            .ok_or_else(|| malformed("missing quality line"))?;
+
+        if !separator.starts_with('+') {
+            return Err(malformed("separator does not start with '+'"));
+        }
+        if qual.len() != seq.len() {
+            return Err(malformed("quality and sequence lengths differ"));
+        }
+
+        records.push(FastqRecord {
+            id,
+            seq: seq.to_string(),
+            qual: qual.to_string(),
+        });
+    }
+
+    Ok(records)
+}
+
+/// A FASTA or FASTQ record; `qual` holds the Phred+33 line for FASTQ only.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeqRecord {
+    pub id: String,
+    pub seq: String,
+    pub qual: Option<String>,
+}
+
+impl From<FastaRecord> for SeqRecord {
+    fn from(record: FastaRecord) -> Self {
+        SeqRecord {
+            id: record.id,
+            seq: record.seq,
+            qual: None,
+        }
+    }
+}
+
+impl From<FastqRecord> for SeqRecord {
+    fn from(record: FastqRecord) -> Self {
+        SeqRecord {
+            id: record.id,
+            seq: record.seq,
+            qual: Some(record.qual),
+        }
+    }
+}
+
+/// Parse FASTA, FASTQ or bare sequence text, picking the format from its
+/// first non-blank character.
+///
+/// `>` starts FASTA and `@` starts FASTQ. Text starting with a letter is one
+/// bare sequence, returned as a single record with an empty id and its
+/// whitespace removed. Blank text gives an empty `Vec`, and a leading UTF-8
+/// byte order mark is skipped. Fails with
+/// [`AssemblyError::Format`] when the text starts with anything else, or when
+/// [`parse_fastq`] rejects it.
+pub fn parse_sequences(raw: &str) -> Result<Vec<SeqRecord>, AssemblyError> {
+    // Some editors start UTF-8 files with a byte order mark //
+    let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+
+    match raw
+        .trim_start_matches(is_sequence_whitespace)
+        .chars()
+        .next()
+    {
+        None => Ok(Vec::new()),
+        Some('>') => Ok(parse_fasta(raw).into_iter().map(SeqRecord::from).collect()),
+        Some('@') => Ok(parse_fastq(raw)?.into_iter().map(SeqRecord::from).collect()),
+        Some(first) if first.is_ascii_alphabetic() => Ok(vec![SeqRecord {
+            id: String::new(),
+            seq: normalize_genome(raw),
+            qual: None,
+        }]),
+        Some(first) => Err(unrecognized_format(first)),
+    }
+}
+
+// Shared with streaming so both report an unknown format the same way //
+pub(crate) fn unrecognized_format(first: char) -> AssemblyError {
+    AssemblyError::Format(format!(
+        "unrecognized sequence format starting with {:?}",
+        first
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_sequence_passes_in_either_case() {
+        assert_eq!(validate_sequence("ACGTNacgtn", DNA_ALPHABET), Ok(()));
+        assert_eq!(validate_sequence("", DNA_ALPHABET), Ok(()));
+    }
+
+    #[test]
+    fn reports_the_position_of_an_invalid_character() {
+        let error = validate_sequence("ACG?T", DNA_ALPHABET).unwrap_err();
+
+        assert_eq!(
+            error,
+            ValidationError {
+                position: 3,
+                byte: b'?'
+            }
+        );
+        assert_eq!(error.to_string(), "invalid character '?' at position 3");
+    }
+
+    #[test]
+    fn ambiguity_codes_pass_only_with_the_iupac_alphabet() {
+        for code in "RYSWKMBDHV".chars() {
+            let seq = format!("AC{}GT", code);
+            assert_eq!(
+                validate_sequence(&seq, DNA_ALPHABET).map_err(|e| e.position),
+                Err(2)
+            );
+            assert_eq!(validate_sequence(&seq, IUPAC_ALPHABET), Ok(()));
+        }
+    }
+
+    #[test]
+    fn prepare_genome_checks_the_sanitized_sequence() {
+        assert_eq!(prepare_genome(">chr\nacg\nt\n").unwrap(), "ACGT");
+        assert_eq!(
+            prepare_genome_with(">chr\nACRT\n", IUPAC_ALPHABET).unwrap(),
+            "ACRT"
+        );
+        assert!(matches!(
+            prepare_genome(">chr\nACRT\n"),
+            Err(AssemblyError::InvalidAlphabet {
+                position: 2,
+                base: 'R'
+            })
+        ));
+        assert!(matches!(
+            prepare_genome(">chr\n\n"),
+            Err(AssemblyError::EmptyInput)
+        ));
+    }
+
+    #[test]
+    fn complements_every_iupac_code() {
+        let pairs = [
+            ('A', 'T'),
+            ('C', 'G'),
+            ('R', 'Y'),
+            ('K', 'M'),
+            ('B', 'V'),
+            ('D', 'H'),
+            ('S', 'S'),
+            ('W', 'W'),
+            ('N', 'N'),
+        ];
+        for (base, expected) in pairs {
+            assert_eq!(complement(base), expected);
+            assert_eq!(complement(expected), base);
+            assert_eq!(
+                complement(base.to_ascii_lowercase()),
+                expected.to_ascii_lowercase()
+            );
+        }
+        assert_eq!(complement('?'), 'N');
+        assert_eq!(complement('x'), 'n');
+    }
+
+    #[test]
+    fn palindromes_are_their_own_reverse_complement() {
+        for seq in ["ACGT", "GAATTC", "AT", "acgt"] {
+            assert_eq!(reverse_complement(seq), seq);
+        }
+        assert_eq!(reverse_complement("AACgRy"), "rYcGTT");
+        assert_eq!(reverse_complement(""), "");
+    }
+
+    #[test]
+    fn normalize_removes_every_kind_of_whitespace() {
+        assert_eq!(normalize_genome("AC GT\r\nTT\tGA\x0B\x0C\n\n"), "ACGTTTGA");
+        // Unicode spaces are left for validation to reject //
+        assert_eq!(normalize_genome("AC\u{00a0}GT"), "AC\u{00a0}GT");
+    }
+
+    #[test]
+    fn validate_alphabet_returns_a_unicode_character_whole() {
+        let path =
+            std::env::temp_dir().join(format!("read_creator_genome_{}.txt", std::process::id()));
+        std::fs::write(&path, "ACGT\nAC\u{00e9}GT\n").unwrap();
+        let genome = normalize_genome(&crate::io::read_genome(path.to_str().unwrap()).unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(validate_alphabet(&genome), Err('\u{00e9}'));
+        assert_eq!(validate_alphabet("ACGTN"), Ok(()));
+        let error = validate_sequence(&genome, DNA_ALPHABET).unwrap_err();
+        assert_eq!(error.position, 6);
+        assert_eq!(error.to_string(), "invalid byte 0xC3 at position 6");
+    }
+
+    #[test]
+    fn fasta_headers_never_reach_the_sequence() {
+        let records = parse_fasta("junk\n>chr1 description ACGT\nACG\nTT\n>chr2\r\nGG\r\n");
+
+        assert_eq!(
+            records,
+            vec![
+                FastaRecord {
+                    id: "chr1".to_string(),
+                    seq: "ACGTT".to_string(),
+                },
+                FastaRecord {
+                    id: "chr2".to_string(),
+                    seq: "GG".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn fasta_record_without_sequence_is_empty() {
+        let records = parse_fasta(">empty\n>full\nAC\n");
+
+        assert_eq!(records[0].seq, "");
+        assert_eq!(records[1].seq, "AC");
+    }
+
+    #[test]
+    fn sanitize_drops_headers_and_uppercases() {
+        assert_eq!(
+            sanitize_genome(">chr1 ACGT\r\nacg t\r\n>chr2\nNNgg\n"),
+            "ACGTNNGG"
+        );
+        assert_eq!(sanitize_genome("ac\ngt"), "ACGT");
+        assert_eq!(sanitize_genome(">only a header\n"), "");
+    }
+
+    #[test]
+    fn fastq_keeps_empty_records_and_skips_blank_lines_between_records() {
+        let records = parse_fastq("@a\n\n+\n\n\n@b\nAC\n+\nII\n").unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            (records[0].seq.as_str(), records[0].qual.as_str()),
+            ("", "")
+        );
+        assert_eq!(
+            (records[1].seq.as_str(), records[1].qual.as_str()),
+            ("AC", "II")
+        );
+    }
+
+    #[test]
+    fn fastq_rejects_malformed_records() {
+        for raw in [
+            "@a\nACGT\n+\nIII\n",
+            "@a\nACGT\n",
+            "a\nAC\n+\nII\n",
+            "@a\nAC\n-\nII\n",
+        ] {
+            assert!(
+                matches!(parse_fastq(raw), Err(AssemblyError::Format(_))),
+                "{:?}",
+                raw
+            );
+        }
+    }
+}
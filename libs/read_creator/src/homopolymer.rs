@@ -0,0 +1,69 @@
+/// Collapse every run of a repeated character in `seq` to one copy.
+///
+/// Returns the compressed sequence and the length of each run, one per
+/// character of the compressed sequence, so [`homopolymer_decompress`] can
+/// restore the original. Characters are compared exactly, so `a` and `A`
+/// form separate runs and case survives the round trip.
+pub fn homopolymer_compress(seq: &str) -> (String, Vec<u32>) {
+    let mut compressed = String::with_capacity(seq.len());
+    let mut runs: Vec<u32> = Vec::new();
+
+    for base in seq.chars() {
+        match (compressed.chars().next_back(), runs.last_mut()) {
+            (Some(last), Some(run)) if last == base => *run += 1,
+            _ => {
+                compressed.push(base);
+                runs.push(1);
+            }
+        }
+    }
+
+    (compressed, runs)
+}
+
+/// Expand each character of `compressed` to its run length in `runs`,
+/// undoing [`homopolymer_compress`].
+///
+/// Panics if `runs` does not hold exactly one length per character.
+pub fn homopolymer_decompress(compressed: &str, runs: &[u32]) -> String {
+    assert_eq!(
+        compressed.chars().count(),
+        runs.len(),
+        "every character needs a run length"
+    );
+
+    compressed
+        .chars()
+        .zip(runs)
+        .flat_map(|(base, &run)| std::iter::repeat_n(base, run as usize))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collapses_runs_and_counts_them() {
+        assert_eq!(
+            homopolymer_compress("AAACGGGGTA"),
+            ("ACGTA".to_string(), vec![3, 1, 4, 1, 1])
+        );
+        assert_eq!(homopolymer_compress(""), (String::new(), vec![]));
+        assert_eq!(homopolymer_compress("aaAA"), ("aA".to_string(), vec![2, 2]));
+    }
+
+    #[test]
+    fn decompress_undoes_compress() {
+        for seq in ["", "A", "AAAA", "ACGT", "TTTGACCCCCAGGN", "aaAAccGGgg"] {
+            let (compressed, runs) = homopolymer_compress(seq);
+            assert_eq!(homopolymer_decompress(&compressed, &runs), seq);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "every character needs a run length")]
+    fn decompress_needs_a_run_per_character() {
+        homopolymer_decompress("AC", &[1]);
+    }
+}
@@ -0,0 +1,744 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+
+use crate::composition::base_composition;
+use crate::error::AssemblyError;
+use crate::genome::{is_sequence_whitespace, parse_sequences, unrecognized_format, SeqRecord};
+use crate::kmer::sorted_counts;
+use crate::mutate::Substitution;
+
+/// Path that stands for stdin when reading and stdout when writing.
+pub const STDIO_PATH: &str = "-";
+
+/// Open `path` for reading, or stdin for [`STDIO_PATH`].
+fn open_input(path: &str) -> Result<Box<dyn Read>, AssemblyError> {
+    if path == STDIO_PATH {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = File::open(path).map_err(AssemblyError::io(path))?;
+    Ok(Box::new(file))
+}
+
+/// Create `path` for buffered writing, or use stdout for [`STDIO_PATH`].
+fn create_output(path: &str) -> Result<Box<dyn Write>, AssemblyError> {
+    if path == STDIO_PATH {
+        return Ok(Box::new(BufWriter::new(io::stdout().lock())));
+    }
+    let file = File::create(path).map_err(AssemblyError::io(path))?;
+    Ok(Box::new(BufWriter::new(file)))
+}
+
+/// Create `path` and hand a buffered writer for it to `write`, tagging any
+/// I/O error with the path. [`STDIO_PATH`] writes to stdout.
+fn write_file(
+    path: &str,
+    write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> Result<(), AssemblyError> {
+    let mut writer = create_output(path)?;
+
+    write(&mut writer)
+        .and_then(|()| writer.flush())
+        .map_err(AssemblyError::io(path))
+}
+
+/// Read the whole genome file at `path` into a string, or all of stdin for
+/// [`STDIO_PATH`].
+///
+/// Fails if the file cannot be opened or is not valid UTF-8.
+pub fn read_genome(path: &str) -> Result<String, AssemblyError> {
+    let mut genome = String::new();
+    let mut reader = BufReader::new(open_input(path)?);
+
+    reader
+        .read_to_string(&mut genome)
+        .map_err(AssemblyError::io(path))?;
+    Ok(genome)
+}
+
+/// Read the FASTA, FASTQ or bare sequence file at `path` into its records,
+/// or stdin for [`STDIO_PATH`].
+///
+/// The format is detected by [`parse_sequences`]: an empty file gives an empty
+/// `Vec`, and a file starting with neither `>`, `@` nor a letter is an error.
+pub fn read_sequences(path: &str) -> Result<Vec<SeqRecord>, AssemblyError> {
+    parse_sequences(&read_genome(path)?)
+}
+
+/// Write `reads` to `path`, one read per line.
+///
+/// Fails if the file cannot be created or written.
+pub fn write_reads(reads: &[String], path: &str) -> Result<(), AssemblyError> {
+    write_file(path, |writer| {
+        for read in reads {
+            writeln!(writer, "{}", read)?;
+        }
+        Ok(())
+    })
+}
+
+/// Whether `path` names a FASTQ file, judged by a `.fq` or `.fastq` extension.
+pub fn is_fastq_path(path: &str) -> bool {
+    let path = path.to_ascii_lowercase();
+    path.ends_with(".fq") || path.ends_with(".fastq")
+}
+
+/// Whether `path` names a FASTA file, judged by a `.fa`, `.fasta` or `.fna`
+/// extension.
+pub fn is_fasta_path(path: &str) -> bool {
+    let path = path.to_ascii_lowercase();
+    path.ends_with(".fa") || path.ends_with(".fasta") || path.ends_with(".fna")
+}
+
+/// Record names `read_1`, `read_2`, ... for `count` single reads.
+pub fn read_names(count: usize) -> Vec<String> {
+    (1..=count).map(|n| format!("read_{}", n)).collect()
+}
+
+/// Record names `pair_1/1`, `pair_1/2`, `pair_2/1`, ... for `pairs` read
+/// pairs, in the interleaved order of their mates.
+pub fn pair_names(pairs: usize) -> Vec<String> {
+    (1..=pairs)
+        .flat_map(|n| [format!("pair_{}/1", n), format!("pair_{}/2", n)])
+        .collect()
+}
+
+/// Write `reads` to `path` as FASTA records named `read_1`, `read_2`, ...
+///
+/// Each sequence is written on a single line. Fails if the file cannot be
+/// created or written.
+pub fn write_reads_fasta(reads: &[String], path: &str) -> Result<(), AssemblyError> {
+    write_reads_fasta_named(reads, &read_names(reads.len()), path)
+}
+
+/// Like [`write_reads_fasta`], with the header line of each read taken from
+/// `headers`, e.g. `pair_1/2` or `read_2 strand=-`.
+///
+/// `headers` must be the same length as `reads`; this panics otherwise.
+pub fn write_reads_fasta_named(
+    reads: &[String],
+    headers: &[String],
+    path: &str,
+) -> Result<(), AssemblyError> {
+    assert_eq!(reads.len(), headers.len(), "every read needs a header");
+
+    write_file(path, |writer| {
+        for (read, header) in reads.iter().zip(headers) {
+            writeln!(writer, ">{}\n{}", header, read)?;
+        }
+        Ok(())
+    })
+}
+
+/// A usual FASTA line width, the default for `--line-width`.
+pub const FASTA_LINE_WIDTH: usize = 80;
+
+/// Write `(header, sequence)` records to `path` as FASTA, breaking each
+/// sequence into lines of `width` bases.
+///
+/// Every sequence line but the last of a record is exactly `width` bases
+/// long, and an empty sequence gets no sequence line at all. Sequences are
+/// split by byte, so they must be ASCII as for
+/// [`crate::reads::break_into_reads`]. Fails if the file cannot be created or
+/// written. Panics if `width` is 0.
+pub fn write_to_fasta_wrapped(
+    records: &[(String, String)],
+    width: usize,
+    path: &str,
+) -> Result<(), AssemblyError> {
+    assert!(width >= 1, "width must be at least 1");
+
+    write_file(path, |writer| {
+        for (header, seq) in records {
+            writeln!(writer, ">{}", header)?;
+            for line in seq.as_bytes().chunks(width) {
+                writer.write_all(line)?;
+                writeln!(writer)?;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Write `reads` to `path` as FASTQ records named `read_1`, `read_2`, ...
+///
+/// `quals` holds the Phred+33 quality line for each read and must be the same
+/// length as `reads`; this panics otherwise. Fails if the file cannot be
+/// created or written.
+pub fn write_reads_fastq(
+    reads: &[String],
+    quals: &[String],
+    path: &str,
+) -> Result<(), AssemblyError> {
+    write_reads_fastq_named(reads, quals, &read_names(reads.len()), path)
+}
+
+/// Like [`write_reads_fastq`], with header lines taken from `headers` as for
+/// [`write_reads_fasta_named`].
+pub fn write_reads_fastq_named(
+    reads: &[String],
+    quals: &[String],
+    headers: &[String],
+    path: &str,
+) -> Result<(), AssemblyError> {
+    assert_eq!(reads.len(), quals.len(), "every read needs a quality line");
+    assert_eq!(reads.len(), headers.len(), "every read needs a header");
+
+    write_file(path, |writer| {
+        for ((read, qual), header) in reads.iter().zip(quals).zip(headers) {
+            writeln!(writer, "@{}\n{}\n+\n{}", header, read, qual)?;
+        }
+        Ok(())
+    })
+}
+
+/// Write a k-mer histogram to `path` as `multiplicity<TAB>distinct_kmers` lines.
+///
+/// Fails if the file cannot be created or written.
+pub fn write_histogram_tsv(
+    histogram: &BTreeMap<u64, u64>,
+    path: &str,
+) -> Result<(), AssemblyError> {
+    write_file(path, |writer| {
+        for (multiplicity, kmers) in histogram {
+            writeln!(writer, "{}\t{}", multiplicity, kmers)?;
+        }
+        Ok(())
+    })
+}
+
+/// Write k-mer counts to `path` as `kmer<TAB>count` lines, most frequent first.
+///
+/// Fails if the file cannot be created or written.
+pub fn write_counts_tsv(counts: &HashMap<String, u64>, path: &str) -> Result<(), AssemblyError> {
+    write_file(path, |writer| {
+        for (kmer, count) in sorted_counts(counts) {
+            writeln!(writer, "{}\t{}", kmer, count)?;
+        }
+        Ok(())
+    })
+}
+
+/// Write injected sequencing errors to `path` as
+/// `read<TAB>position<TAB>from<TAB>to` lines, where `read` is the record
+/// name of the read, `names[read_index]`. Pass the names the reads were
+/// written under, such as [`read_names`] or [`pair_names`]; positions are
+/// 0-based.
+///
+/// Panics if a read index is out of range for `names`. Fails if the file
+/// cannot be created or written.
+pub fn write_substitutions_tsv(
+    errors: &[(usize, Substitution)],
+    names: &[String],
+    path: &str,
+) -> Result<(), AssemblyError> {
+    write_file(path, |writer| {
+        for (read_index, error) in errors {
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}",
+                names[*read_index], error.position, error.from, error.to
+            )?;
+        }
+        Ok(())
+    })
+}
+
+/// Bytes read from the genome file at a time by [`stream_reads`].
+const STREAM_CHUNK: usize = 64 * 1024;
+
+/// The UTF-8 byte order mark some editors put at the start of a file.
+const BYTE_ORDER_MARK: &[u8] = b"\xEF\xBB\xBF";
+
+// A lone byte as a character for error messages; streaming never decodes UTF-8 //
+fn byte_char(byte: u8) -> char {
+    if byte.is_ascii() {
+        byte as char
+    } else {
+        char::REPLACEMENT_CHARACTER
+    }
+}
+
+/// Break the genome at `input_path` into reads and write them to
+/// `output_path` one per line, without loading the whole genome.
+/// [`STDIO_PATH`] reads from stdin or writes to stdout.
+///
+/// The output is byte-identical to running [`read_sequences`],
+/// [`crate::genome::prepare_genome_with`] on the joined records,
+/// [`crate::reads::break_into_reads`] and [`write_reads`] in turn: the format
+/// is sniffed the same way, a byte order mark, FASTA headers and
+/// [`crate::genome::is_sequence_whitespace`] are skipped, bases are
+/// uppercased and anything outside `alphabet` (given in uppercase) is
+/// rejected. FASTQ input fails with [`AssemblyError::Format`], since its
+/// quality lines cannot be told from bases without parsing whole records.
+/// The genome is read in fixed-size chunks and only the bases that a later
+/// read still needs are kept between chunks, so memory stays bounded by the
+/// chunk size plus `read_length`. Returns the number of reads written.
+/// Panics if `step` is 0.
+pub fn stream_reads(
+    input_path: &str,
+    output_path: &str,
+    read_length: usize,
+    step: usize,
+    alphabet: &[u8],
+) -> Result<usize, AssemblyError> {
+    assert!(step >= 1, "step must be at least 1");
+
+    let mut reader = open_input(input_path)?;
+    let mut writer = create_output(output_path)?;
+
+    let mut chunk = vec![0u8; STREAM_CHUNK];
+    // Bases not yet consumed; `pending[0]` sits at genome offset `pending_start` //
+    let mut pending: Vec<u8> = Vec::new();
+    let mut pending_start = 0;
+    let mut genome_len = 0;
+    let mut next_read = 0;
+    let mut reads = 0;
+    // Bytes of the input seen so far, and how many began a byte order mark //
+    let mut input_offset = 0;
+    let mut bom_len = 0;
+    // `None` until the first non-blank byte, then whether the input is FASTA //
+    let mut fasta = None;
+    let mut at_line_start = true;
+    let mut in_header = false;
+    let mut seen_header = false;
+
+    loop {
+        let n = reader
+            .read(&mut chunk)
+            .map_err(AssemblyError::io(input_path))?;
+        if n == 0 {
+            break;
+        }
+
+        for &byte in &chunk[..n] {
+            // A byte order mark is only skipped at the very start //
+            let offset = input_offset;
+            input_offset += 1;
+            if offset == bom_len
+                && bom_len < BYTE_ORDER_MARK.len()
+                && byte == BYTE_ORDER_MARK[bom_len]
+            {
+                bom_len += 1;
+                continue;
+            }
+
+            // Track lines so FASTA headers can be skipped //
+            if byte == b'\n' {
+                at_line_start = true;
+                in_header = false;
+                continue;
+            }
+            let line_start = std::mem::replace(&mut at_line_start, false);
+            if is_sequence_whitespace(char::from(byte)) {
+                continue;
+            }
+
+            // Sniff the format from the first non-blank byte, as read_sequences does //
+            if fasta.is_none() {
+                fasta = Some(match byte {
+                    b'>' => true,
+                    b'@' => {
+                        return Err(AssemblyError::Format(
+                            "--stream does not support FASTQ input.".to_string(),
+                        ))
+                    }
+                    byte if byte.is_ascii_alphabetic() => false,
+                    byte => return Err(unrecognized_format(byte_char(byte))),
+                });
+            }
+            if fasta == Some(true) {
+                if line_start && byte == b'>' {
+                    in_header = true;
+                    seen_header = true;
+                }
+                // Lines before the first header are ignored, as by parse_fasta //
+                if in_header || !seen_header {
+                    continue;
+                }
+            }
+
+            let base = byte.to_ascii_uppercase();
+            if !alphabet.contains(&base) {
+                return Err(AssemblyError::InvalidAlphabet {
+                    position: genome_len,
+                    base: byte_char(byte),
+                });
+            }
+
+            pending.push(base);
+            genome_len += 1;
+        }
+
+        // Emit every read that now fits //
+        while next_read + read_length <= genome_len {
+            let start = next_read - pending_start;
+            writer
+                .write_all(&pending[start..start + read_length])
+                .and_then(|()| writer.write_all(b"\n"))
+                .map_err(AssemblyError::io(output_path))?;
+            reads += 1;
+            next_read += step;
+        }
+
+        // Keep only the bases from the next read onwards //
+        let consumed = (next_read - pending_start).min(pending.len());
+        pending.drain(..consumed);
+        pending_start += consumed;
+    }
+
+    if genome_len == 0 {
+        return Err(AssemblyError::EmptyInput);
+    }
+
+    writer.flush().map_err(AssemblyError::io(output_path))?;
+    Ok(reads)
+}
+
+/// Write the length and composition of each read to `path` as TSV, with a
+/// `read  length  A  C  G  T  N  other  gc` header and 1-based read numbers.
+///
+/// Fails if the file cannot be created or written.
+pub fn write_read_stats_tsv(reads: &[String], path: &str) -> Result<(), AssemblyError> {
+    write_file(path, |writer| {
+        writeln!(writer, "read\tlength\tA\tC\tG\tT\tN\tother\tgc")?;
+        for (n, read) in reads.iter().enumerate() {
+            let c = base_composition(read);
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{:.4}",
+                n + 1,
+                c.total(),
+                c.a,
+                c.c,
+                c.g,
+                c.t,
+                c.n,
+                c.other,
+                c.gc_content()
+            )?;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::{parse_fasta, parse_fastq};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    // A fresh path under the temp directory, unique to this test process //
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("read_creator_io_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn fasta_records_are_named_in_order() {
+        let path = scratch("named.fa");
+        let path = path.to_str().unwrap();
+
+        write_reads_fasta(&strings(&["ACGT", "GGA"]), path).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            ">read_1\nACGT\n>read_2\nGGA\n"
+        );
+        assert!(is_fasta_path("reads.FNA") && !is_fasta_path("reads.fq"));
+    }
+
+    #[test]
+    fn wrapped_fasta_lines_are_exactly_the_width() {
+        let path = scratch("wrapped.fa");
+        let path = path.to_str().unwrap();
+        let records: Vec<(String, String)> = [("a", 25), ("b", 20), ("c", 7), ("d", 0)]
+            .iter()
+            .map(|&(header, len)| {
+                (
+                    header.to_string(),
+                    "ACGTTGCA".chars().cycle().take(len).collect(),
+                )
+            })
+            .collect();
+
+        write_to_fasta_wrapped(&records, 10, path).unwrap();
+        let text = fs::read_to_string(path).unwrap();
+
+        let mut lengths: Vec<Vec<usize>> = Vec::new();
+        for line in text.lines() {
+            match line.strip_prefix('>') {
+                Some(_) => lengths.push(Vec::new()),
+                None => lengths.last_mut().unwrap().push(line.len()),
+            }
+        }
+        assert_eq!(lengths, [vec![10, 10, 5], vec![10, 10], vec![7], vec![]]);
+        let seqs: Vec<String> = parse_fasta(&text).into_iter().map(|r| r.seq).collect();
+        assert_eq!(
+            seqs,
+            records.into_iter().map(|(_, seq)| seq).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn fastq_records_are_four_lines_each() {
+        let path = scratch("four_lines.fq");
+        let path = path.to_str().unwrap();
+
+        write_reads_fastq(&strings(&["ACGT", "GGA"]), &strings(&["IIII", "III"]), path).unwrap();
+        let text = fs::read_to_string(path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 8);
+        for record in lines.chunks(4) {
+            assert!(record[0].starts_with('@'));
+            assert_eq!(record[2], "+");
+            assert_eq!(record[1].len(), record[3].len());
+        }
+        assert!(text.ends_with('\n'));
+    }
+
+    #[test]
+    #[should_panic(expected = "every read needs a quality line")]
+    fn fastq_needs_a_quality_line_per_read() {
+        let path = scratch("missing_qual.fq");
+        write_reads_fastq(&strings(&["ACGT"]), &[], path.to_str().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn fastq_output_parses_back_to_the_same_reads() {
+        let path = scratch("round_trip.fq");
+        let path = path.to_str().unwrap();
+        let reads = strings(&["ACGT", "TTGCA", "G"]);
+        let quals = strings(&["II#!", "56789", "~"]);
+
+        write_reads_fastq(&reads, &quals, path).unwrap();
+        let records = parse_fastq(&read_genome(path).unwrap()).unwrap();
+
+        assert_eq!(records.len(), reads.len());
+        for (i, record) in records.iter().enumerate() {
+            assert_eq!(record.id, format!("read_{}", i + 1));
+            assert_eq!(record.seq, reads[i]);
+            assert_eq!(record.qual, quals[i]);
+        }
+    }
+
+    #[test]
+    fn pair_names_share_an_id_per_pair() {
+        assert_eq!(
+            pair_names(2),
+            strings(&["pair_1/1", "pair_1/2", "pair_2/1", "pair_2/2"])
+        );
+        assert_eq!(read_names(2), strings(&["read_1", "read_2"]));
+    }
+
+    #[test]
+    fn named_fasta_uses_the_given_headers() {
+        let path = scratch("named.fa");
+        let path = path.to_str().unwrap();
+        let reads = strings(&["ACGT", "ACGT"]);
+
+        write_reads_fasta_named(&reads, &pair_names(1), path).unwrap();
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            ">pair_1/1\nACGT\n>pair_1/2\nACGT\n"
+        );
+    }
+
+    fn read_file(name: &str, contents: &str) -> Result<Vec<SeqRecord>, AssemblyError> {
+        let path = scratch(name);
+        fs::write(&path, contents).unwrap();
+        read_sequences(path.to_str().unwrap())
+    }
+
+    #[test]
+    fn read_sequences_parses_fasta_files() {
+        let records = read_file("seqs.fa", ">one first\nACG\nT\n>two\nGG\n").unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            (records[0].id.as_str(), records[0].seq.as_str()),
+            ("one", "ACGT")
+        );
+        assert_eq!(
+            (records[1].id.as_str(), records[1].seq.as_str()),
+            ("two", "GG")
+        );
+        assert!(records.iter().all(|record| record.qual.is_none()));
+    }
+
+    #[test]
+    fn read_sequences_parses_fastq_files() {
+        let records = read_file("seqs.fq", "@r1\nACGT\n+\nIIII\n@r2\nGG\n+\n#I\n").unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].seq, "ACGT");
+        assert_eq!(records[1].qual.as_deref(), Some("#I"));
+    }
+
+    #[test]
+    fn read_sequences_of_an_empty_file_is_empty() {
+        assert!(read_file("empty.txt", "").unwrap().is_empty());
+        assert!(read_file("blank.txt", "\n  \n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn read_sequences_takes_bare_text_as_one_record() {
+        let records = read_file("bare.txt", "ACGT\nTTGA\n").unwrap();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].seq, "ACGTTTGA");
+    }
+
+    #[test]
+    fn read_sequences_skips_a_byte_order_mark() {
+        let fasta = read_file("bom.fa", "\u{feff}>chr\nACGT\n").unwrap();
+        let bare = read_file("bom.txt", "\u{feff}ACGT\n").unwrap();
+
+        assert_eq!(
+            (fasta[0].id.as_str(), fasta[0].seq.as_str()),
+            ("chr", "ACGT")
+        );
+        assert_eq!(bare[0].seq, "ACGT");
+    }
+
+    #[test]
+    fn read_sequences_rejects_an_unknown_format() {
+        assert!(matches!(
+            read_file("unknown.txt", "#comment\nACGT\n"),
+            Err(AssemblyError::Format(_))
+        ));
+    }
+
+    // A multi-FASTA genome with CRLF line breaks, mixed case and ~200 kb of bases //
+    fn medium_fasta() -> String {
+        let mut rng = crate::rng::Rng::new(7);
+        let mut raw = String::new();
+        for record in 0..4 {
+            raw.push_str(&format!(">contig_{} length=50000\r\n", record));
+            for _ in 0..(50_000 / 60) {
+                let line: String = (0..60)
+                    .map(|_| b"ACGTacgtN"[rng.range_inclusive(0, 8)] as char)
+                    .collect();
+                raw.push_str(&line);
+                raw.push_str("\r\n");
+            }
+        }
+        raw
+    }
+
+    // The genome the in-memory path of the binary cuts reads from //
+    fn in_memory_genome(path: &str) -> Result<String, AssemblyError> {
+        let seq: String = read_sequences(path)?
+            .into_iter()
+            .map(|record| record.seq)
+            .collect();
+        crate::genome::prepare_genome(&seq)
+    }
+
+    fn stream(input: &Path, read_length: usize, step: usize) -> Result<usize, AssemblyError> {
+        stream_reads(
+            input.to_str().unwrap(),
+            scratch("streamed.txt").to_str().unwrap(),
+            read_length,
+            step,
+            crate::genome::DNA_ALPHABET,
+        )
+    }
+
+    #[test]
+    fn streaming_matches_in_memory_output() {
+        // Bare text with a byte order mark and vertical tabs between lines //
+        let bare = format!(
+            "\u{feff}{}",
+            medium_fasta()
+                .lines()
+                .filter(|line| !line.starts_with('>'))
+                .collect::<Vec<_>>()
+                .join("\x0B\n")
+        );
+        let inputs = [("medium.fa", medium_fasta()), ("medium.txt", bare)];
+
+        for (name, raw) in inputs {
+            let input = scratch(name);
+            fs::write(&input, &raw).unwrap();
+            let genome = in_memory_genome(input.to_str().unwrap()).unwrap();
+
+            for (read_length, step) in [(100, 1), (100, 37), (100, 100), (50, 120)] {
+                let in_memory = scratch("in_memory.txt");
+                let reads = crate::reads::break_into_reads(&genome, read_length, step);
+                write_reads(&reads, in_memory.to_str().unwrap()).unwrap();
+
+                assert_eq!(stream(&input, read_length, step).unwrap(), reads.len());
+                assert_eq!(
+                    fs::read(scratch("streamed.txt")).unwrap(),
+                    fs::read(&in_memory).unwrap(),
+                    "{} read_length {} step {}",
+                    name,
+                    read_length,
+                    step
+                );
+            }
+        }
+
+        // FASTQ is read in memory but refused when streaming //
+        let fastq = scratch("stream.fq");
+        fs::write(&fastq, "@r1\nACGTAC\n+\nIIIIII\n").unwrap();
+        assert_eq!(in_memory_genome(fastq.to_str().unwrap()).unwrap(), "ACGTAC");
+        assert!(matches!(
+            stream(&fastq, 3, 1),
+            Err(AssemblyError::Format(message)) if message.contains("FASTQ")
+        ));
+    }
+
+    #[test]
+    fn streaming_sniffs_the_format_like_read_sequences() {
+        let input = scratch("sniff.txt");
+        for (raw, expected) in [
+            // A `>` line inside bare text is a bad base, not a header //
+            ("ACGT\n>chr\nACGT\n", None),
+            ("  >chr\nACGT\n>chr2\nGGCC\n", Some(3)),
+            ("#comment\nACGT\n", None),
+        ] {
+            fs::write(&input, raw).unwrap();
+            let in_memory = in_memory_genome(input.to_str().unwrap()).ok();
+            let streamed = stream(&input, 2, 1).ok();
+
+            assert_eq!(streamed, expected, "{:?}", raw);
+            assert_eq!(
+                in_memory.map(|genome| genome.len() - 1),
+                expected,
+                "{:?}",
+                raw
+            );
+        }
+    }
+
+    #[test]
+    fn streaming_reports_the_offset_of_a_bad_base() {
+        let input = scratch("bad_stream.fa");
+        fs::write(&input, ">chr\nACGT\nAC?T\n").unwrap();
+        let output = scratch("bad_stream.txt");
+
+        let result = stream_reads(
+            input.to_str().unwrap(),
+            output.to_str().unwrap(),
+            2,
+            1,
+            crate::genome::DNA_ALPHABET,
+        );
+        assert!(matches!(
+            result,
+            Err(AssemblyError::InvalidAlphabet {
+                position: 6,
+                base: '?'
+            })
+        ));
+    }
+}
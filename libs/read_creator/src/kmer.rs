@@ -0,0 +1,229 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::genome::reverse_complement;
+use crate::reads::break_into_reads;
+
+/// Count every length-`k` window across `reads`, merging each k-mer with its
+/// reverse complement.
+///
+/// Shorthand for [`count_kmers_with`] with `canonical` set.
+pub fn count_kmers(reads: &[String], k: usize) -> HashMap<String, u64> {
+    count_kmers_with(reads, k, true)
+}
+
+/// Count every length-`k` window across `reads`.
+///
+/// Windows slide one base at a time as in [`break_into_reads`], so a read shorter
+/// than `k` contributes nothing and no partial k-mer is emitted at a read end.
+/// K-mers containing `N` are skipped. With `canonical` set each k-mer is
+/// keyed by [`canonical_kmer`], so both strands share one count.
+pub fn count_kmers_with(reads: &[String], k: usize, canonical: bool) -> HashMap<String, u64> {
+    let mut counts = HashMap::new();
+
+    for read in reads {
+        for kmer in break_into_reads(read, k, 1) {
+            if kmer.contains(['N', 'n']) {
+                continue;
+            }
+
+            let kmer = if canonical {
+                canonical_kmer(&kmer)
+            } else {
+                kmer
+            };
+            *counts.entry(kmer).or_insert(0) += 1;
+        }
+    }
+
+    counts
+}
+
+/// The lexicographically smaller of `kmer` and its reverse complement.
+///
+/// For even `k` a k-mer can be its own reverse complement (e.g. `ACGT`), in
+/// which case it is returned unchanged.
+pub fn canonical_kmer(kmer: &str) -> String {
+    let rc = reverse_complement(kmer);
+    if rc.as_str() < kmer {
+        rc
+    } else {
+        kmer.to_string()
+    }
+}
+
+/// K-mer counts ordered by descending count, ties broken by k-mer.
+pub fn sorted_counts(counts: &HashMap<String, u64>) -> Vec<(&str, u64)> {
+    let mut sorted: Vec<(&str, u64)> = counts.iter().map(|(kmer, &n)| (kmer.as_str(), n)).collect();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    sorted
+}
+
+/// Map each multiplicity to the number of distinct k-mers seen that many times.
+pub fn kmer_histogram(counts: &HashMap<String, u64>) -> BTreeMap<u64, u64> {
+    let mut histogram = BTreeMap::new();
+
+    for &count in counts.values() {
+        *histogram.entry(count).or_insert(0) += 1;
+    }
+
+    histogram
+}
+
+/// Fix single substitution errors in `reads` using k-mer support.
+///
+/// K-mers are counted across all reads (canonically, so both strands share
+/// support). A k-mer seen fewer than `min_count` times is weak. For each read
+/// containing weak k-mers, every single-base substitution at a position
+/// covered by a weak k-mer is tried, and it is accepted only if it makes
+/// every k-mer overlapping that position solid. The read is corrected only
+/// when exactly one such substitution exists; otherwise, and for reads
+/// without weak k-mers, it is returned unchanged. At most one base is changed
+/// per read, and reads containing `N` are left alone. Panics if `k` is 0.
+pub fn correct_reads(reads: &[String], k: usize, min_count: u64) -> Vec<String> {
+    assert!(k >= 1, "k must be at least 1");
+
+    let counts = count_kmers(reads, k);
+    let solid = |kmer: &str| {
+        counts
+            .get(&canonical_kmer(kmer))
+            .is_some_and(|&n| n >= min_count)
+    };
+
+    reads
+        .iter()
+        .map(|read| correct_read(read, k, &solid).unwrap_or_else(|| read.clone()))
+        .collect()
+}
+
+// The single substitution that makes `read` solid around the changed base, if unique //
+fn correct_read(read: &str, k: usize, solid: &impl Fn(&str) -> bool) -> Option<String> {
+    if read.len() < k || read.contains(['N', 'n']) {
+        return None;
+    }
+
+    let last_start = read.len() - k;
+    let mut positions: Vec<usize> = (0..=last_start)
+        .filter(|&i| !solid(&read[i..i + k]))
+        .flat_map(|i| i..i + k)
+        .collect();
+    positions.sort_unstable();
+    positions.dedup();
+
+    let mut fixes = Vec::new();
+    for position in positions {
+        let original = read.as_bytes()[position].to_ascii_uppercase();
+
+        for base in ["A", "C", "G", "T"] {
+            if base.as_bytes()[0] == original {
+                continue;
+            }
+
+            let mut candidate = read.to_string();
+            candidate.replace_range(position..position + 1, base);
+
+            // Every window that covers the changed base must now be solid //
+            let first = position.saturating_sub(k - 1);
+            if (first..=position.min(last_start)).all(|i| solid(&candidate[i..i + k])) {
+                fixes.push(candidate);
+            }
+        }
+    }
+
+    match fixes.len() {
+        1 => fixes.pop(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every window of `genome`, `copies` times over //
+    fn reads_of(genome: &str, read_length: usize, copies: usize) -> Vec<String> {
+        let reads = break_into_reads(genome, read_length, 1);
+        (0..copies).flat_map(|_| reads.clone()).collect()
+    }
+
+    fn substitute(read: &str, position: usize, base: &str) -> String {
+        let mut read = read.to_string();
+        read.replace_range(position..position + 1, base);
+        read
+    }
+
+    #[test]
+    fn counts_and_histogram_of_a_tiny_read_set() {
+        let reads = [
+            String::from("ACGTA"),
+            String::from("CGTAC"),
+            String::from("AC"),
+        ];
+        let counts = count_kmers_with(&reads, 3, false);
+
+        assert_eq!(
+            sorted_counts(&counts),
+            [("CGT", 2), ("GTA", 2), ("ACG", 1), ("TAC", 1)]
+        );
+        assert_eq!(
+            kmer_histogram(&counts).into_iter().collect::<Vec<_>>(),
+            [(1, 2), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn canonical_counts_merge_both_strands() {
+        let counts = count_kmers(&[String::from("ACG"), String::from("CGT")], 3);
+
+        assert_eq!(sorted_counts(&counts), [("ACG", 2)]);
+    }
+
+    #[test]
+    fn a_kmer_and_its_reverse_complement_share_a_bucket() {
+        let counts = count_kmers(&[String::from("AAAT"), String::from("ATTT")], 4);
+
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts.get("AAAT"), Some(&2));
+        assert_eq!(canonical_kmer("ATTT"), "AAAT");
+        assert_eq!(canonical_kmer("ACGT"), "ACGT");
+    }
+
+    #[test]
+    fn kmers_with_n_are_skipped() {
+        let counts = count_kmers_with(&[String::from("ACNGTA")], 2, false);
+
+        let mut kmers: Vec<&str> = counts.keys().map(String::as_str).collect();
+        kmers.sort_unstable();
+        assert_eq!(kmers, ["AC", "GT", "TA"]);
+    }
+
+    #[test]
+    fn corrects_a_single_injected_error() {
+        let genome = "ACGGTCATTGCAGTTCAGCCTA";
+        let mut reads = reads_of(genome, 12, 3);
+        let original = reads[0].clone();
+        let with_error = substitute(&original, 6, "G");
+        reads.push(with_error.clone());
+
+        let corrected = correct_reads(&reads, 5, 2);
+
+        assert_ne!(with_error, original);
+        assert_eq!(corrected.last(), Some(&original));
+        assert_eq!(corrected[..reads.len() - 1], reads[..reads.len() - 1]);
+    }
+
+    #[test]
+    fn leaves_a_read_alone_when_two_fixes_compete() {
+        // Two haplotypes differ at offset 6 (A or C), each well supported //
+        let first = "ACGGTCATTGCAGTTCAGCCTA";
+        let second = substitute(first, 6, "C");
+        let mut reads = reads_of(first, 12, 2);
+        reads.extend(reads_of(&second, 12, 2));
+        // A third base there could be fixed towards either one //
+        let with_error = substitute(&first[..12], 6, "G");
+        reads.push(with_error.clone());
+
+        let corrected = correct_reads(&reads, 5, 2);
+
+        assert_eq!(corrected.last(), Some(&with_error));
+    }
+}
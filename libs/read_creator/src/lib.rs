@@ -0,0 +1,19 @@
+//! Read simulation from a genome sequence.
+//!
+//! The `read_creator` binary is a thin command-line wrapper around these
+//! modules; they can also be used directly as a library.
+
+pub mod complexity;
+pub mod composition;
+pub mod dedup;
+pub mod encoding;
+pub mod error;
+pub mod genome;
+pub mod homopolymer;
+pub mod io;
+pub mod kmer;
+pub mod minhash;
+pub mod mutate;
+pub mod quality;
+pub mod reads;
+pub mod rng;
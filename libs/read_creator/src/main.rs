@@ -1,59 +1,860 @@
 use std::env;
-use std::fs::File;
-use std::io::{self, Read, Write, BufReader, BufWriter};
 
-fn break_into_reads(genome: &str, read_length: usize) -> Vec<String> {
-    let mut reads = Vec::new();
-    // Use a while loop to control the step size of i //
-    let mut i = 0;
-    while i + read_length <= genome.len() {
-        let read = &genome[i..i + read_length];
-        reads.push(read.to_string());
-        i += 1; // Iterate by 1 every time //
+use read_creator::complexity::{is_low_complexity, mask_low_complexity};
+use read_creator::composition::base_composition;
+use read_creator::dedup::{redundant_reads, strand_redundant_reads};
+use read_creator::error::AssemblyError;
+use read_creator::genome::{prepare_genome_with, DNA_ALPHABET, IUPAC_ALPHABET};
+use read_creator::io::{
+    is_fasta_path, is_fastq_path, pair_names, read_names, read_sequences, stream_reads,
+    write_counts_tsv, write_histogram_tsv, write_read_stats_tsv, write_reads,
+    write_reads_fasta_named, write_reads_fastq_named, write_substitutions_tsv,
+    write_to_fasta_wrapped, FASTA_LINE_WIDTH, STDIO_PATH,
+};
+use read_creator::kmer::{correct_reads, count_kmers_with, kmer_histogram};
+use read_creator::mutate::{mutate_read_with_errors, Substitution};
+use read_creator::quality::trim_quality;
+use read_creator::reads::{
+    add_reverse_strand, break_into_reads, break_into_reads_variable, drop_flagged, sample_reads,
+    simulate_pairs, subsample_flags,
+};
+use read_creator::rng::Rng;
+
+const USAGE: &str = "<input> <output> <kmer_length> [options]
+
+An <input> or <output> of - reads stdin or writes stdout; giving only
+<kmer_length> does both.
+
+Options:
+  --validate <set>     Accept input bases from dna (ACGTN, default) or iupac
+                       (ACGTN plus ambiguity codes)
+  --stats              Print the base composition of the input genome
+  --read-stats <path>  Write the length and composition of every read as TSV
+  --min-qual <q>       Trim FASTQ input records to Phred quality q, dropping any
+                       left shorter than kmer_length
+  --step <n>           Start a new read every n bases (default: 1); consecutive reads
+                       overlap by kmer_length - n bases, and n > kmer_length leaves gaps
+  --stream             Write reads while reading the genome in chunks, keeping memory
+                       bounded; only --step may be combined with it, and the
+                       input must be FASTA or bare sequence
+  --max-length <n>     Draw read lengths from kmer_length..=n instead of a fixed length
+  --coverage <x>       Sample reads from random positions to about x-fold coverage
+  --both-strands <p>   Follow each read with its reverse complement with probability p,
+                       tagged strand=- in the headers of FASTA or FASTQ output
+  --subsample <x>      Randomly drop reads until they cover the genome about x-fold
+  --insert-size <n>    Simulate FR read pairs from fragments of mean length n,
+                       written to <output>_R1 and <output>_R2
+  --insert-sd <n>      Standard deviation of the fragment length (default: n / 10)
+  --interleaved        Write read pairs to <output> with mates in adjacent records;
+                       either way mates are named pair_N/1 and pair_N/2
+  --error-rate <p>     Substitute each base of the reads with probability p (default: 0)
+  --errors <path>      Write every injected error as read, position, from, to TSV;
+                       needs --error-rate
+  --seed <n>           Seed for the random choices of the simulation (default: 0)
+  --correct <n>        Fix single-base errors using k-mers seen at least n times
+  --low-complexity <mode>
+                       mask low-complexity regions of the reads with N, or
+                       filter out reads that contain one
+  --complexity-window <n>
+                       Window scored by --low-complexity (default: 64)
+  --complexity-threshold <x>
+                       Triplet entropy from 0 to 1 below which a window is low
+                       complexity (default: 0.5)
+  --dedup              Drop exact duplicate reads, keeping the first copy
+  --dedup-contained    Also drop reads contained in a longer read (implies --dedup)
+  --dedup-rc           Also drop reverse complements of earlier reads (implies --dedup)
+  --wrap               Break FASTA output sequences into lines of --line-width bases
+  --line-width <n>     Line width for --wrap (default: 80)
+  --counts <path>      Write k-mer counts of the reads as TSV, most frequent first
+  --histogram <path>   Write a k-mer multiplicity histogram of the reads as TSV
+  --count-k <k>        K-mer size for --counts, --histogram and --correct
+                       (default: kmer_length)
+  --canonical          Count a k-mer and its reverse complement together (default)
+  --no-canonical       Count a k-mer and its reverse complement separately";
+
+// Progress messages go to stderr when the reads themselves go to stdout //
+macro_rules! status {
+    ($options:expr, $($arg:tt)*) => {
+        if $options.output_file_path == STDIO_PATH {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+// Exit codes: 2 for bad arguments, 1 for everything else //
+const EXIT_USAGE: i32 = 2;
+const EXIT_FAILURE: i32 = 1;
+
+struct Options {
+    input_file_path: String,
+    output_file_path: String,
+    read_length: usize,
+    alphabet: &'static [u8],
+    stats: bool,
+    read_stats_path: Option<String>,
+    min_qual: Option<u8>,
+    step: usize,
+    stream: bool,
+    max_length: Option<usize>,
+    coverage: Option<f64>,
+    both_strands: Option<f64>,
+    subsample: Option<f64>,
+    insert_size: Option<f64>,
+    insert_sd: Option<f64>,
+    interleaved: bool,
+    error_rate: f64,
+    errors_path: Option<String>,
+    seed: u64,
+    correct: Option<u64>,
+    low_complexity: Option<LowComplexity>,
+    complexity_window: usize,
+    complexity_threshold: f64,
+    dedup: bool,
+    dedup_contained: bool,
+    dedup_rc: bool,
+    // Set by --wrap //
+    line_width: Option<usize>,
+    counts_path: Option<String>,
+    histogram_path: Option<String>,
+    count_k: Option<usize>,
+    canonical: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LowComplexity {
+    Mask,
+    Filter,
+}
+
+fn parse_positive(name: &str, value: &str) -> Result<usize, AssemblyError> {
+    match value.parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(AssemblyError::Usage(format!(
+            "{} must be a positive integer.",
+            name
+        ))),
     }
+}
 
-    reads
+fn parse_alphabet(value: &str) -> Result<&'static [u8], AssemblyError> {
+    match value {
+        "dna" => Ok(DNA_ALPHABET),
+        "iupac" => Ok(IUPAC_ALPHABET),
+        _ => Err(AssemblyError::Usage(
+            "--validate must be dna or iupac.".to_string(),
+        )),
+    }
 }
 
-fn main() -> io::Result<()> {
-    // Parse command-line arguments //
-    let args: Vec<String> = env::args().collect();
+fn parse_low_complexity(value: &str) -> Result<LowComplexity, AssemblyError> {
+    match value {
+        "mask" => Ok(LowComplexity::Mask),
+        "filter" => Ok(LowComplexity::Filter),
+        _ => Err(AssemblyError::Usage(
+            "--low-complexity must be mask or filter.".to_string(),
+        )),
+    }
+}
 
-    if args.len() < 2 {
-        eprintln!("Usage: {} <kmer_length>", args[0]);
-        std::process::exit(1);
+fn parse_coverage(name: &str, value: &str) -> Result<f64, AssemblyError> {
+    match value.parse::<f64>() {
+        Ok(x) if x.is_finite() && x > 0.0 => Ok(x),
+        _ => Err(AssemblyError::Usage(format!(
+            "{} must be a positive number.",
+            name
+        ))),
     }
+}
 
-    let read_length: usize = match args[1].parse() {
-        Ok(n) => n,
-        Err(_) => {
-            eprintln!("Error: kmer_length must be a positive integer.");
-            std::process::exit(1);
+fn parse_length(name: &str, value: &str) -> Result<f64, AssemblyError> {
+    match value.parse::<f64>() {
+        Ok(x) if x.is_finite() && x >= 0.0 => Ok(x),
+        _ => Err(AssemblyError::Usage(format!(
+            "{} must be a non-negative number.",
+            name
+        ))),
+    }
+}
+
+fn parse_rate(name: &str, value: &str) -> Result<f64, AssemblyError> {
+    match value.parse::<f64>() {
+        Ok(p) if (0.0..=1.0).contains(&p) => Ok(p),
+        _ => Err(AssemblyError::Usage(format!(
+            "{} must be between 0 and 1.",
+            name
+        ))),
+    }
+}
+
+fn parse_quality(value: &str) -> Result<u8, AssemblyError> {
+    value
+        .parse()
+        .map_err(|_| AssemblyError::Usage("--min-qual must be between 0 and 255.".to_string()))
+}
+
+fn parse_seed(value: &str) -> Result<u64, AssemblyError> {
+    value
+        .parse()
+        .map_err(|_| AssemblyError::Usage("--seed must be a non-negative integer.".to_string()))
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Options, AssemblyError> {
+    let mut args = args;
+    let mut positional = Vec::new();
+    let mut alphabet = DNA_ALPHABET;
+    let mut stats = false;
+    let mut read_stats_path = None;
+    let mut min_qual = None;
+    let mut step = 1;
+    let mut stream = false;
+    let mut max_length = None;
+    let mut coverage = None;
+    let mut both_strands = None;
+    let mut subsample = None;
+    let mut insert_size = None;
+    let mut insert_sd = None;
+    let mut interleaved = false;
+    let mut error_rate = 0.0;
+    let mut errors_path = None;
+    let mut seed = 0;
+    let mut correct = None;
+    let mut low_complexity = None;
+    let mut complexity_window = None;
+    let mut complexity_threshold = None;
+    let mut dedup = false;
+    let mut dedup_contained = false;
+    let mut dedup_rc = false;
+    let mut wrap = false;
+    let mut line_width = None;
+    let mut counts_path = None;
+    let mut histogram_path = None;
+    let mut count_k = None;
+    let mut canonical = true;
+
+    while let Some(arg) = args.next() {
+        // Flags that take a value pull it from the next argument //
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| AssemblyError::Usage(format!("{} needs a value.", arg)))
+        };
+
+        match arg.as_str() {
+            "--validate" => alphabet = parse_alphabet(&value()?)?,
+            "--stats" => stats = true,
+            "--read-stats" => read_stats_path = Some(value()?),
+            "--min-qual" => min_qual = Some(parse_quality(&value()?)?),
+            "--step" => step = parse_positive("--step", &value()?)?,
+            "--stream" => stream = true,
+            "--max-length" => max_length = Some(parse_positive("--max-length", &value()?)?),
+            "--coverage" => coverage = Some(parse_coverage("--coverage", &value()?)?),
+            "--both-strands" => both_strands = Some(parse_rate("--both-strands", &value()?)?),
+            "--subsample" => subsample = Some(parse_coverage("--subsample", &value()?)?),
+            "--insert-size" => insert_size = Some(parse_length("--insert-size", &value()?)?),
+            "--insert-sd" => insert_sd = Some(parse_length("--insert-sd", &value()?)?),
+            "--interleaved" => interleaved = true,
+            "--error-rate" => error_rate = parse_rate("--error-rate", &value()?)?,
+            "--errors" => errors_path = Some(value()?),
+            "--seed" => seed = parse_seed(&value()?)?,
+            "--correct" => correct = Some(parse_positive("--correct", &value()?)? as u64),
+            "--low-complexity" => low_complexity = Some(parse_low_complexity(&value()?)?),
+            "--complexity-window" => {
+                complexity_window = Some(parse_positive("--complexity-window", &value()?)?)
+            }
+            "--complexity-threshold" => {
+                complexity_threshold = Some(parse_rate("--complexity-threshold", &value()?)?)
+            }
+            "--dedup" => dedup = true,
+            "--dedup-contained" => dedup_contained = true,
+            "--dedup-rc" => dedup_rc = true,
+            "--wrap" => wrap = true,
+            "--line-width" => line_width = Some(parse_positive("--line-width", &value()?)?),
+            "--counts" => counts_path = Some(value()?),
+            "--histogram" => histogram_path = Some(value()?),
+            "--count-k" => count_k = Some(parse_positive("--count-k", &value()?)?),
+            "--canonical" => canonical = true,
+            "--no-canonical" => canonical = false,
+            flag if flag.starts_with("--") => {
+                return Err(AssemblyError::Usage(format!("unknown option {}.", flag)))
+            }
+            _ => positional.push(arg),
         }
+    }
+
+    // A lone kmer_length pipes stdin to stdout //
+    if positional.len() == 1 {
+        positional.splice(0..0, [STDIO_PATH.to_string(), STDIO_PATH.to_string()]);
+    }
+
+    if positional.len() != 3 {
+        return Err(AssemblyError::Usage(
+            "expected <input> <output> <kmer_length>.".to_string(),
+        ));
+    }
+
+    let read_length = parse_positive("kmer_length", &positional[2])?;
+
+    if max_length.is_some_and(|max| max < read_length) {
+        return Err(AssemblyError::Usage(
+            "--max-length must not be less than kmer_length.".to_string(),
+        ));
+    }
+
+    // Variable-length reads always start at every base //
+    if max_length.is_some() && step != 1 {
+        return Err(AssemblyError::Usage(
+            "--step cannot be combined with --max-length.".to_string(),
+        ));
+    }
+
+    // Sampled reads pick their own start positions //
+    if coverage.is_some() && (step != 1 || max_length.is_some()) {
+        return Err(AssemblyError::Usage(
+            "--coverage cannot be combined with --step or --max-length.".to_string(),
+        ));
+    }
+
+    // Pairs are placed at random like sampled reads, optionally to a coverage //
+    if insert_size.is_some() && (step != 1 || max_length.is_some()) {
+        return Err(AssemblyError::Usage(
+            "--insert-size cannot be combined with --step or --max-length.".to_string(),
+        ));
+    }
+
+    // Dropping one mate would unpair the rest of the file //
+    if insert_size.is_some() && (dedup || dedup_contained || dedup_rc) {
+        return Err(AssemblyError::Usage(
+            "--dedup cannot be combined with --insert-size.".to_string(),
+        ));
+    }
+
+    if insert_size.is_some() && low_complexity == Some(LowComplexity::Filter) {
+        return Err(AssemblyError::Usage(
+            "--low-complexity filter cannot be combined with --insert-size.".to_string(),
+        ));
+    }
+
+    if low_complexity.is_none() && (complexity_window.is_some() || complexity_threshold.is_some()) {
+        return Err(AssemblyError::Usage(
+            "--complexity-window and --complexity-threshold need --low-complexity.".to_string(),
+        ));
+    }
+
+    if insert_size.is_some() && subsample.is_some() {
+        return Err(AssemblyError::Usage(
+            "--subsample cannot be combined with --insert-size.".to_string(),
+        ));
+    }
+
+    // Second mates are already reverse strand //
+    if insert_size.is_some() && both_strands.is_some() {
+        return Err(AssemblyError::Usage(
+            "--both-strands cannot be combined with --insert-size.".to_string(),
+        ));
+    }
+
+    // Only FASTA and FASTQ headers have room for the strand tag //
+    if both_strands.is_some() && !is_fasta_path(&positional[1]) && !is_fastq_path(&positional[1]) {
+        return Err(AssemblyError::Usage(
+            "--both-strands needs FASTA or FASTQ output.".to_string(),
+        ));
+    }
+
+    // Separate R1 and R2 files cannot both be stdout //
+    if insert_size.is_some() && !interleaved && positional[1] == STDIO_PATH {
+        return Err(AssemblyError::Usage(
+            "--insert-size to stdout needs --interleaved.".to_string(),
+        ));
+    }
+
+    if line_width.is_some() && !wrap {
+        return Err(AssemblyError::Usage(
+            "--line-width needs --wrap.".to_string(),
+        ));
+    }
+
+    // Only FASTA allows a sequence to span lines //
+    if wrap && !is_fasta_path(&positional[1]) {
+        return Err(AssemblyError::Usage(
+            "--wrap needs FASTA output.".to_string(),
+        ));
+    }
+
+    // Without errors to inject there is nothing to record //
+    if errors_path.is_some() && error_rate == 0.0 {
+        return Err(AssemblyError::Usage(
+            "--errors needs --error-rate.".to_string(),
+        ));
+    }
+
+    if insert_size.is_none() && (insert_sd.is_some() || interleaved) {
+        return Err(AssemblyError::Usage(
+            "--insert-sd and --interleaved need --insert-size.".to_string(),
+        ));
+    }
+
+    let options = Options {
+        read_length,
+        output_file_path: positional.swap_remove(1),
+        input_file_path: positional.swap_remove(0),
+        alphabet,
+        stats,
+        read_stats_path,
+        min_qual,
+        step,
+        stream,
+        max_length,
+        coverage,
+        both_strands,
+        subsample,
+        insert_size,
+        insert_sd,
+        interleaved,
+        error_rate,
+        errors_path,
+        seed,
+        correct,
+        low_complexity,
+        complexity_window: complexity_window.unwrap_or(64),
+        complexity_threshold: complexity_threshold.unwrap_or(0.5),
+        dedup: dedup || dedup_contained || dedup_rc,
+        dedup_contained,
+        dedup_rc,
+        line_width: wrap.then(|| line_width.unwrap_or(FASTA_LINE_WIDTH)),
+        counts_path,
+        histogram_path,
+        count_k,
+        canonical,
     };
 
-    let input_file_path = "/home/mikhailu/Genetics_Code/Genomes/Cholerae/Vibrio_cholerae.txt";
-    let output_file_path = "vibrio_cholerae_segments.txt";
+    if options.stream && !streamable(&options) {
+        return Err(AssemblyError::Usage(
+            "--stream only supports plain-text output with --step.".to_string(),
+        ));
+    }
 
-    // Read the genome from the input file //
-    let input_file = File::open(input_file_path)?;
-    let mut genome = String::new();
-    let mut reader = BufReader::new(input_file);
+    Ok(options)
+}
 
-    reader.read_to_string(&mut genome)?;
+// Streaming only cuts fixed windows and writes them as plain lines //
+fn streamable(options: &Options) -> bool {
+    let path = &options.output_file_path;
 
-    // Generate the kmers //
-    let reads = break_into_reads(&genome, read_length);
+    !options.stats
+        && options.read_stats_path.is_none()
+        && options.min_qual.is_none()
+        && options.max_length.is_none()
+        && options.coverage.is_none()
+        && options.both_strands.is_none()
+        && options.subsample.is_none()
+        && options.insert_size.is_none()
+        && options.error_rate == 0.0
+        && options.correct.is_none()
+        && options.low_complexity.is_none()
+        && !options.dedup
+        && options.counts_path.is_none()
+        && options.histogram_path.is_none()
+        && !is_fasta_path(path)
+        && !is_fastq_path(path)
+}
 
-    // Write the new reads to the output file //
-    let output_file = File::create(output_file_path)?;
-    let mut writer = BufWriter::new(output_file);
+fn write_kmer_reports(options: &Options, reads: &[String]) -> Result<(), AssemblyError> {
+    let k = options.count_k.unwrap_or(options.read_length);
+    let counts = count_kmers_with(reads, k, options.canonical);
 
-    for read in reads {
-        writeln!(writer, "{}", read)?;
+    if let Some(counts_path) = &options.counts_path {
+        write_counts_tsv(&counts, counts_path)?;
+        status!(options, "K-mer counts written to {}", counts_path);
+    }
+
+    if let Some(histogram_path) = &options.histogram_path {
+        write_histogram_tsv(&kmer_histogram(&counts), histogram_path)?;
+        status!(options, "K-mer histogram written to {}", histogram_path);
     }
 
-    println!("Reads written to {}", output_file_path);
     Ok(())
 }
 
+// Reads and everything that must stay aligned with them as some are dropped //
+struct ReadSet {
+    reads: Vec<String>,
+    quals: Option<Vec<String>>,
+    // Whether each read is an added reverse-strand copy //
+    reverse: Vec<bool>,
+    // Injected errors as (read index, substitution) //
+    errors: Vec<(usize, Substitution)>,
+}
+
+impl ReadSet {
+    // Drop the flagged reads and everything attached to them, renumbering //
+    // the errors of the rest; returns how many reads went //
+    fn drop_flagged(&mut self, flags: &[bool]) -> usize {
+        let mut new_index = Vec::with_capacity(flags.len());
+        let mut kept = 0;
+        for &flagged in flags {
+            new_index.push((!flagged).then_some(kept));
+            kept += usize::from(!flagged);
+        }
+        let removed = self.reads.len() - kept;
+
+        self.reads = drop_flagged(std::mem::take(&mut self.reads), flags);
+        self.quals = self.quals.take().map(|quals| drop_flagged(quals, flags));
+        self.reverse = drop_flagged(std::mem::take(&mut self.reverse), flags);
+        self.errors = std::mem::take(&mut self.errors)
+            .into_iter()
+            .filter_map(|(index, error)| new_index[index].map(|index| (index, error)))
+            .collect();
+
+        removed
+    }
+}
+
+// Write reads as FASTA or FASTQ if the extension asks for it, plain lines //
+// otherwise. Plain lines have nowhere to put headers, so --both-strands rejects them. //
+// FASTA sequences are wrapped at `line_width` if given //
+fn write_output(
+    reads: &[String],
+    quals: Option<Vec<String>>,
+    headers: &[String],
+    line_width: Option<usize>,
+    path: &str,
+) -> Result<(), AssemblyError> {
+    if is_fastq_path(path) {
+        // Without input qualities every base gets the same high score //
+        let quals =
+            quals.unwrap_or_else(|| reads.iter().map(|read| "I".repeat(read.len())).collect());
+        write_reads_fastq_named(reads, &quals, headers, path)
+    } else if is_fasta_path(path) {
+        match line_width {
+            Some(width) => {
+                let records: Vec<(String, String)> =
+                    headers.iter().cloned().zip(reads.iter().cloned()).collect();
+                write_to_fasta_wrapped(&records, width, path)
+            }
+            None => write_reads_fasta_named(reads, headers, path),
+        }
+    } else {
+        write_reads(reads, path)
+    }
+}
+
+// reads.fq -> (reads_R1.fq, reads_R2.fq); the suffix goes before the extension //
+fn mate_paths(path: &str) -> (String, String) {
+    let name_start = path.rfind('/').map_or(0, |i| i + 1);
+    let split = match path[name_start..].find('.') {
+        Some(i) if i > 0 => name_start + i,
+        _ => path.len(),
+    };
+    let (stem, extension) = path.split_at(split);
+
+    (
+        format!("{}_R1{}", stem, extension),
+        format!("{}_R2{}", stem, extension),
+    )
+}
+
+fn run(options: &Options) -> Result<(), AssemblyError> {
+    let output_file_path = &options.output_file_path;
+
+    if options.stream {
+        let reads = stream_reads(
+            &options.input_file_path,
+            output_file_path,
+            options.read_length,
+            options.step,
+            options.alphabet,
+        )?;
+        status!(options, "{} reads written to {}", reads, output_file_path);
+        return Ok(());
+    }
+
+    // Read the genome, dropping FASTA headers and line breaks and rejecting //
+    // anything that is not a nucleotide before slicing. Records are //
+    // concatenated, with FASTQ qualities kept alongside //
+    let mut records = read_sequences(&options.input_file_path)?;
+    let fastq = records.iter().any(|record| record.qual.is_some());
+
+    // Only FASTQ input has qualities to trim by //
+    if options.min_qual.is_some() && !fastq {
+        return Err(AssemblyError::Format(
+            "--min-qual needs FASTQ input.".to_string(),
+        ));
+    }
+
+    if let Some(min_q) = options.min_qual {
+        let before = records.len();
+        for record in records.iter_mut() {
+            if let Some(qual) = &mut record.qual {
+                (record.seq, *qual) = trim_quality(&record.seq, qual, min_q);
+            }
+        }
+        records.retain(|record| record.seq.len() >= options.read_length);
+        status!(
+            options,
+            "Dropped {} records shorter than {} after quality trimming",
+            before - records.len(),
+            options.read_length
+        );
+    }
+
+    let seq: String = records.iter().map(|r| r.seq.as_str()).collect();
+    let genome = prepare_genome_with(&seq, options.alphabet)?;
+    let genome_qual: Option<String> = records.iter().map(|r| r.qual.as_deref()).collect();
+
+    if genome_qual
+        .as_ref()
+        .is_some_and(|qual| genome.len() != qual.len())
+    {
+        return Err(AssemblyError::Format(
+            "FASTQ sequence contains whitespace.".to_string(),
+        ));
+    }
+
+    if options.stats {
+        status!(options, "{}", base_composition(&genome));
+    }
+
+    // Generate the kmers. Every mode only depends on the length and the seed, //
+    // so cutting the quality string the same way keeps it aligned with the reads //
+    let cut = |seq: &str| match (options.coverage, options.max_length) {
+        (Some(coverage), _) => sample_reads(seq, options.read_length, coverage, options.seed),
+        (None, Some(max_length)) => {
+            break_into_reads_variable(seq, options.read_length, max_length, options.seed)
+        }
+        (None, None) => break_into_reads(seq, options.read_length, options.step),
+    };
+    let (reads, quals) = match options.insert_size {
+        // Mates go into one list as pair 1 R1, pair 1 R2, pair 2 R1, ... and //
+        // get uniform qualities, since a reverse mate has no input quality //
+        Some(insert_mean) => {
+            let coverage = options.coverage.unwrap_or(1.0);
+            let count = (coverage * genome.len() as f64 / (2 * options.read_length) as f64).round()
+                as usize;
+            let insert_sd = options.insert_sd.unwrap_or(insert_mean / 10.0);
+            let pairs = simulate_pairs(
+                &genome,
+                options.read_length,
+                insert_mean,
+                insert_sd,
+                count,
+                options.seed,
+            );
+            let reads = pairs
+                .into_iter()
+                .flat_map(|pair| [pair.first, pair.second])
+                .collect();
+            (reads, None)
+        }
+        None => (cut(&genome), genome_qual.as_deref().map(cut)),
+    };
+
+    let mut set = ReadSet {
+        reverse: vec![false; reads.len()],
+        reads,
+        quals,
+        errors: Vec::new(),
+    };
+
+    // Add reverse-strand copies, flagged so the output can tag them //
+    if let Some(probability) = options.both_strands {
+        // Offset the seed so the strands are independent of the other choices //
+        let mut rng = Rng::new(options.seed.wrapping_add(3));
+        (set.reads, set.reverse) = add_reverse_strand(set.reads, probability, &mut rng);
+        // A reverse read's qualities are its forward read's, reversed //
+        let reverse = &set.reverse;
+        set.quals = set.quals.map(|quals| {
+            let mut quals = quals.into_iter();
+            reverse
+                .iter()
+                .scan(String::new(), |last, &rc| {
+                    if rc {
+                        Some(last.chars().rev().collect())
+                    } else {
+                        *last = quals.next().unwrap_or_default();
+                        Some(last.clone())
+                    }
+                })
+                .collect()
+        });
+    }
+
+    // Thin the reads to the target coverage, keeping qualities aligned //
+    if let Some(target) = options.subsample {
+        // Offset the seed so the choice is independent of the reads and errors //
+        let dropped = subsample_flags(
+            &set.reads,
+            target,
+            genome.len(),
+            options.seed.wrapping_add(2),
+        );
+        let removed = set.drop_flagged(&dropped);
+        status!(options, "Subsampled away {} reads", removed);
+    }
+
+    // Inject sequencing errors after the reads are cut //
+    if options.error_rate > 0.0 {
+        // Offset the seed so the errors are independent of the read lengths //
+        let mut rng = Rng::new(options.seed.wrapping_add(1));
+
+        for (read_index, read) in set.reads.iter_mut().enumerate() {
+            let (mutated, read_errors) =
+                mutate_read_with_errors(read, options.error_rate, &mut rng);
+            set.errors
+                .extend(read_errors.into_iter().map(|error| (read_index, error)));
+            *read = mutated;
+        }
+
+        status!(
+            options,
+            "Introduced {} substitution errors",
+            set.errors.len()
+        );
+    }
+
+    // Correct errors before deduplicating, so fixed reads can collapse together //
+    if let Some(min_count) = options.correct {
+        let k = options.count_k.unwrap_or(options.read_length);
+        let corrected = correct_reads(&set.reads, k, min_count);
+        let changed = set
+            .reads
+            .iter()
+            .zip(&corrected)
+            .filter(|(a, b)| a != b)
+            .count();
+
+        set.reads = corrected;
+        status!(options, "Corrected {} reads", changed);
+    }
+
+    // Mask or drop repetitive stretches that would tangle the overlaps //
+    let (window, threshold) = (options.complexity_window, options.complexity_threshold);
+    match options.low_complexity {
+        Some(LowComplexity::Mask) => {
+            for read in set.reads.iter_mut() {
+                *read = mask_low_complexity(read, window, threshold);
+            }
+            status!(options, "Masked low-complexity regions of the reads");
+        }
+        Some(LowComplexity::Filter) => {
+            let flagged: Vec<bool> = set
+                .reads
+                .iter()
+                .map(|read| is_low_complexity(read, window, threshold))
+                .collect();
+            let removed = set.drop_flagged(&flagged);
+            status!(options, "Removed {} low-complexity reads", removed);
+        }
+        None => {}
+    }
+
+    // Drop redundant reads, and their qualities and errors with them //
+    if options.dedup {
+        let mut redundant = redundant_reads(&set.reads, options.dedup_contained);
+        if options.dedup_rc {
+            for (flag, rc) in redundant.iter_mut().zip(strand_redundant_reads(&set.reads)) {
+                *flag |= rc;
+            }
+        }
+        let removed = set.drop_flagged(&redundant);
+        status!(options, "Removed {} redundant reads", removed);
+    }
+
+    // Name mates pair_N/1 and pair_N/2 so they can be matched up again, and //
+    // tag reverse-strand copies in their headers //
+    let ReadSet {
+        reads,
+        quals,
+        reverse,
+        errors,
+    } = set;
+    let names = if options.insert_size.is_some() {
+        pair_names(reads.len() / 2)
+    } else {
+        read_names(reads.len())
+    };
+    let headers: Vec<String> = names
+        .iter()
+        .zip(&reverse)
+        .map(|(name, &reverse)| {
+            if reverse {
+                format!("{} strand=-", name)
+            } else {
+                name.clone()
+            }
+        })
+        .collect();
+
+    // Write the new reads, splitting mates into R1 and R2 files unless interleaved //
+    if options.insert_size.is_some() && !options.interleaved {
+        let (first_path, second_path) = mate_paths(output_file_path);
+        let mate = |list: &[String], skip: usize| -> Vec<String> {
+            list.iter().skip(skip).step_by(2).cloned().collect()
+        };
+
+        let width = options.line_width;
+        write_output(
+            &mate(&reads, 0),
+            None,
+            &mate(&headers, 0),
+            width,
+            &first_path,
+        )?;
+        write_output(
+            &mate(&reads, 1),
+            None,
+            &mate(&headers, 1),
+            width,
+            &second_path,
+        )?;
+        status!(
+            options,
+            "Read pairs written to {} and {}",
+            first_path,
+            second_path
+        );
+    } else {
+        write_output(
+            &reads,
+            quals,
+            &headers,
+            options.line_width,
+            output_file_path,
+        )?;
+        status!(options, "Reads written to {}", output_file_path);
+    }
+
+    // Written last so every error refers to a read that made it into the output //
+    if let Some(errors_path) = &options.errors_path {
+        write_substitutions_tsv(&errors, &names, errors_path)?;
+        status!(options, "Injected errors written to {}", errors_path);
+    }
+
+    if let Some(read_stats_path) = &options.read_stats_path {
+        write_read_stats_tsv(&reads, read_stats_path)?;
+        status!(options, "Read statistics written to {}", read_stats_path);
+    }
+
+    // Optionally dump the k-mer counts and spectrum of the reads //
+    if options.counts_path.is_some() || options.histogram_path.is_some() {
+        write_kmer_reports(options, &reads)?;
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let mut args = env::args();
+    let program = args.next().unwrap_or_else(|| "read_creator".to_string());
+
+    if let Err(e) = parse_args(args).and_then(|options| run(&options)) {
+        eprintln!("Error: {}", e);
+
+        // Only a bad command line is worth the usage text //
+        if let AssemblyError::Usage(_) = e {
+            eprintln!("Usage: {} {}", program, USAGE);
+            std::process::exit(EXIT_USAGE);
+        }
+        std::process::exit(EXIT_FAILURE);
+    }
+}
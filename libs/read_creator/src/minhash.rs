@@ -0,0 +1,167 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::kmer::canonical_kmer;
+use crate::reads::break_into_reads;
+
+/// A bottom-k MinHash sketch of the k-mers in a set of sequences, for a quick
+/// estimate of how similar two read sets or assemblies are.
+///
+/// The sketch keeps the `size` smallest hashes of the distinct canonical
+/// k-mers, so both strands of a sequence give the same sketch. K-mers are
+/// uppercased first and those containing `N` are skipped, as in
+/// [`crate::kmer::count_kmers_with`]. Hashes come from
+/// [`DefaultHasher`], which is fixed within one build but not across Rust
+/// releases, so only compare sketches built by the same binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinHash {
+    k: usize,
+    size: usize,
+    // The smallest distinct hashes seen, ascending //
+    hashes: Vec<u64>,
+}
+
+impl MinHash {
+    /// Sketch every length-`k` window of `seqs`, keeping at most `size`
+    /// hashes. Panics if `k` or `size` is 0.
+    pub fn new(seqs: &[String], k: usize, size: usize) -> Self {
+        assert!(k >= 1, "k must be at least 1");
+        assert!(size >= 1, "sketch size must be at least 1");
+
+        let mut smallest = BTreeSet::new();
+        for seq in seqs {
+            for kmer in break_into_reads(seq, k, 1) {
+                if kmer.contains(['N', 'n']) {
+                    continue;
+                }
+
+                let hash = hash_kmer(&canonical_kmer(&kmer.to_ascii_uppercase()));
+                if smallest.len() < size {
+                    smallest.insert(hash);
+                } else if smallest.last().is_some_and(|&max| hash < max) && smallest.insert(hash) {
+                    smallest.pop_last();
+                }
+            }
+        }
+
+        MinHash {
+            k,
+            size,
+            hashes: smallest.into_iter().collect(),
+        }
+    }
+
+    /// The k-mer length the sketch was built with.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Estimated Jaccard similarity of the two k-mer sets, from 0 (nothing
+    /// shared) to 1 (identical).
+    ///
+    /// Takes the smallest hashes of the union of both sketches, up to the
+    /// smaller sketch size, and returns the share of them found in both. Two
+    /// empty sketches score 0. Panics if the sketches use different `k`.
+    pub fn jaccard(&self, other: &MinHash) -> f64 {
+        assert_eq!(self.k, other.k, "sketches must use the same k");
+
+        let size = self.size.min(other.size);
+        let (a, b) = (&self.hashes, &other.hashes);
+        let (mut i, mut j) = (0, 0);
+        let (mut taken, mut shared) = (0, 0);
+
+        // Merge the sorted hash lists until `size` hashes of the union are seen //
+        while taken < size {
+            let order = match (a.get(i), b.get(j)) {
+                (Some(x), Some(y)) => x.cmp(y),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match order {
+                Ordering::Equal => {
+                    shared += 1;
+                    i += 1;
+                    j += 1;
+                }
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+            }
+            taken += 1;
+        }
+
+        match taken {
+            0 => 0.0,
+            taken => shared as f64 / taken as f64,
+        }
+    }
+}
+
+fn hash_kmer(kmer: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    kmer.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::reverse_complement;
+    use crate::rng::Rng;
+
+    // Random ACGT reads, the same for the same seed //
+    fn random_reads(count: usize, len: usize, seed: u64) -> Vec<String> {
+        let mut rng = Rng::new(seed);
+        (0..count)
+            .map(|_| {
+                (0..len)
+                    .map(|_| ['A', 'C', 'G', 'T'][rng.range_inclusive(0, 3)])
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn identical_inputs_score_one() {
+        let reads = random_reads(50, 100, 1);
+        let sketch = MinHash::new(&reads, 21, 200);
+
+        assert_eq!(sketch.jaccard(&MinHash::new(&reads, 21, 200)), 1.0);
+        // Canonical k-mers make the other strand identical too //
+        let rc: Vec<String> = reads.iter().map(|read| reverse_complement(read)).collect();
+        assert_eq!(sketch.jaccard(&MinHash::new(&rc, 21, 200)), 1.0);
+    }
+
+    #[test]
+    fn disjoint_inputs_score_zero() {
+        let first = MinHash::new(&random_reads(50, 100, 1), 21, 200);
+        let second = MinHash::new(&random_reads(50, 100, 2), 21, 200);
+
+        assert_eq!(first.jaccard(&second), 0.0);
+        assert_eq!(MinHash::new(&[], 21, 200).jaccard(&first), 0.0);
+    }
+
+    #[test]
+    fn partial_overlap_is_estimated_near_the_true_jaccard() {
+        // Half of each set is shared, so the true Jaccard is 1/3 //
+        let (shared, only_a, only_b) = (
+            random_reads(40, 100, 3),
+            random_reads(40, 100, 4),
+            random_reads(40, 100, 5),
+        );
+        let a = MinHash::new(&[shared.clone(), only_a].concat(), 21, 500);
+        let b = MinHash::new(&[shared, only_b].concat(), 21, 500);
+
+        let estimate = a.jaccard(&b);
+        assert!((estimate - 1.0 / 3.0).abs() < 0.08, "estimate {}", estimate);
+    }
+
+    #[test]
+    fn sketch_ignores_case_and_n_kmers() {
+        let upper = MinHash::new(&[String::from("ACGTTGCA")], 4, 10);
+
+        assert_eq!(upper, MinHash::new(&[String::from("acgttgca")], 4, 10));
+        assert_eq!(MinHash::new(&[String::from("ACNT")], 2, 10).hashes.len(), 1);
+    }
+}
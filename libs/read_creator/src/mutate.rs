@@ -0,0 +1,121 @@
+use crate::rng::Rng;
+
+const BASES: [char; 4] = ['A', 'C', 'G', 'T'];
+
+/// One base changed by [`mutate_read_with_errors`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Substitution {
+    /// Offset of the base within the read.
+    pub position: usize,
+    pub from: char,
+    pub to: char,
+}
+
+/// Copy `read`, substituting each base with probability `error_rate`.
+///
+/// Same as [`mutate_read_with_errors`] without the error record.
+pub fn mutate_read(read: &str, error_rate: f64, rng: &mut Rng) -> String {
+    mutate_read_with_errors(read, error_rate, rng).0
+}
+
+/// Copy `read`, substituting each base with probability `error_rate`, and
+/// list every substitution made.
+///
+/// A substituted base always becomes one of the three other nucleotides,
+/// chosen uniformly, and keeps its case. Characters other than A, C, G and T
+/// (such as N) are never mutated. Panics if `error_rate` is outside `[0, 1]`.
+pub fn mutate_read_with_errors(
+    read: &str,
+    error_rate: f64,
+    rng: &mut Rng,
+) -> (String, Vec<Substitution>) {
+    assert!(
+        (0.0..=1.0).contains(&error_rate),
+        "error_rate must be between 0 and 1"
+    );
+
+    let mut errors = Vec::new();
+    let mutated = read
+        .chars()
+        .enumerate()
+        .map(|(position, base)| {
+            let upper = base.to_ascii_uppercase();
+            if !BASES.contains(&upper) || rng.next_f64() >= error_rate {
+                return base;
+            }
+
+            let others: Vec<char> = BASES.iter().copied().filter(|&b| b != upper).collect();
+            let mut substitute = others[rng.range_inclusive(0, others.len() - 1)];
+            if base.is_ascii_lowercase() {
+                substitute = substitute.to_ascii_lowercase();
+            }
+
+            errors.push(Substitution {
+                position,
+                from: base,
+                to: substitute,
+            });
+            substitute
+        })
+        .collect();
+
+    (mutated, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const READ: &str = "ACGTTGCAGGCTAGCATCGATTGACGATCGGCTAGCATG";
+
+    #[test]
+    fn zero_rate_returns_the_read_unchanged() {
+        let mut rng = Rng::new(11);
+        assert_eq!(mutate_read(READ, 0.0, &mut rng), READ);
+    }
+
+    #[test]
+    fn full_rate_changes_every_base_but_n_and_keeps_case() {
+        let mut rng = Rng::new(11);
+        let mutated = mutate_read("ACgtN", 1.0, &mut rng);
+
+        for (before, after) in "ACgtN".chars().zip(mutated.chars()) {
+            match before {
+                'N' => assert_eq!(after, 'N'),
+                _ => {
+                    assert_ne!(before.to_ascii_uppercase(), after.to_ascii_uppercase());
+                    assert_eq!(before.is_ascii_lowercase(), after.is_ascii_lowercase());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_errors() {
+        let first = mutate_read_with_errors(READ, 0.2, &mut Rng::new(3));
+
+        assert_eq!(first, mutate_read_with_errors(READ, 0.2, &mut Rng::new(3)));
+        assert_ne!(first, mutate_read_with_errors(READ, 0.2, &mut Rng::new(4)));
+    }
+
+    #[test]
+    fn recorded_errors_are_exactly_the_changed_bases() {
+        let (mutated, errors) = mutate_read_with_errors(READ, 0.2, &mut Rng::new(3));
+        assert!(!errors.is_empty());
+
+        let diff: Vec<Substitution> = READ
+            .chars()
+            .zip(mutated.chars())
+            .enumerate()
+            .filter(|(_, (from, to))| from != to)
+            .map(|(position, (from, to))| Substitution { position, from, to })
+            .collect();
+        assert_eq!(errors, diff);
+    }
+
+    #[test]
+    #[should_panic(expected = "error_rate must be between 0 and 1")]
+    fn rate_above_one_panics() {
+        mutate_read(READ, 1.5, &mut Rng::new(11));
+    }
+}
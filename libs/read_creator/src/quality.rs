@@ -0,0 +1,85 @@
+/// Phred score of one Phred+33 quality character.
+pub fn phred(qual: u8) -> u8 {
+    qual.saturating_sub(b'!')
+}
+
+/// Length of the low-quality run at the start of `quals`, by running sum.
+///
+/// Walks inwards summing `min_q - q` and cuts where the sum peaks, so a
+/// single good base inside a bad stretch does not stop the trim.
+fn trim_point(quals: impl Iterator<Item = u8>, min_q: u8) -> usize {
+    let mut sum = 0i64;
+    let mut best = 0i64;
+    let mut cut = 0;
+
+    for (i, q) in quals.enumerate() {
+        sum += i64::from(min_q) - i64::from(phred(q));
+        if sum < 0 {
+            break;
+        }
+        if sum > best {
+            best = sum;
+            cut = i + 1;
+        }
+    }
+
+    cut
+}
+
+/// Trim low-quality bases from both ends of a read.
+///
+/// `qual` is Phred+33 encoded and must be the same length as `seq`; this
+/// panics otherwise. Each end is trimmed with a running-sum rule against
+/// `min_q` (as in BWA's `-q`), which may trim the read away entirely.
+pub fn trim_quality(seq: &str, qual: &str, min_q: u8) -> (String, String) {
+    assert_eq!(seq.len(), qual.len(), "sequence and quality lengths differ");
+
+    let bytes = qual.as_bytes();
+    let start = trim_point(bytes.iter().copied(), min_q);
+    let end = bytes.len() - trim_point(bytes.iter().rev().copied(), min_q);
+
+    if start >= end {
+        return (String::new(), String::new());
+    }
+
+    (seq[start..end].to_string(), qual[start..end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phred_decodes_phred33() {
+        assert_eq!(phred(b'!'), 0);
+        assert_eq!(phred(b'#'), 2);
+        assert_eq!(phred(b'I'), 40);
+    }
+
+    #[test]
+    fn trims_the_expected_number_of_bases_from_each_end() {
+        let (seq, qual) = trim_quality("AAAAACCCCCGG", "#####IIIII##", 20);
+
+        // Five bases go from the start and two from the end //
+        assert_eq!(seq, "CCCCC");
+        assert_eq!(qual, "IIIII");
+    }
+
+    #[test]
+    fn one_good_base_does_not_stop_the_trim() {
+        let (seq, _) = trim_quality("AACAAGGG", "##I##III", 20);
+        assert_eq!(seq, "GGG");
+    }
+
+    #[test]
+    fn good_reads_are_kept_and_bad_reads_trimmed_away() {
+        assert_eq!(
+            trim_quality("ACGT", "IIII", 20),
+            ("ACGT".to_string(), "IIII".to_string())
+        );
+        assert_eq!(
+            trim_quality("ACGT", "####", 20),
+            (String::new(), String::new())
+        );
+    }
+}
@@ -0,0 +1,417 @@
+use crate::genome::reverse_complement;
+use crate::rng::Rng;
+
+/// Break `genome` into windows of `read_length` bases, starting a new window
+/// every `step` bases.
+///
+/// A `step` of 1 gives a sliding window over every position, and a `step`
+/// equal to `read_length` gives non-overlapping tiles. Consecutive reads
+/// overlap by `read_length - step` bases, so to get an overlap of `k` pick
+/// `step = read_length - k`; a `step` larger than `read_length` skips the
+/// bases in between. Trailing bases that do
+/// not fill a whole window are dropped, so this returns an empty `Vec` when
+/// the genome is shorter than `read_length`.
+///
+/// The genome is sliced by byte offset, so it must be ASCII; run
+/// [`crate::genome::validate_sequence`] first to guarantee that, otherwise
+/// this panics on a multi-byte character boundary. Panics if `step` is 0.
+pub fn break_into_reads(genome: &str, read_length: usize, step: usize) -> Vec<String> {
+    assert!(step >= 1, "step must be at least 1");
+
+    let mut reads = Vec::new();
+    // Use a while loop to control the step size of i //
+    let mut i = 0;
+    while i + read_length <= genome.len() {
+        let read = &genome[i..i + read_length];
+        reads.push(read.to_string());
+        i += step;
+    }
+
+    reads
+}
+
+/// Break `genome` into reads whose lengths are drawn uniformly from
+/// `min_len..=max_len`, starting a new read at every base.
+///
+/// Lengths come from an [`Rng`] seeded with `seed`, so the same inputs always
+/// give the same reads. A read near the end of the genome is clamped to the
+/// bases that remain rather than skipped, and walking stops once fewer than
+/// `min_len` bases are left, so every read length stays within the range.
+///
+/// The genome must be ASCII as for [`break_into_reads`]. Panics if `min_len`
+/// is 0 or greater than `max_len`.
+pub fn break_into_reads_variable(
+    genome: &str,
+    min_len: usize,
+    max_len: usize,
+    seed: u64,
+) -> Vec<String> {
+    assert!(min_len >= 1, "min_len must be at least 1");
+    assert!(min_len <= max_len, "min_len must not exceed max_len");
+
+    let mut rng = Rng::new(seed);
+    let mut reads = Vec::new();
+    let mut i = 0;
+    while i + min_len <= genome.len() {
+        let read_length = rng.range_inclusive(min_len, max_len).min(genome.len() - i);
+        reads.push(genome[i..i + read_length].to_string());
+        i += 1;
+    }
+
+    reads
+}
+
+/// Sample reads of `read_length` bases from uniformly random start positions
+/// until the genome is covered roughly `coverage` times.
+///
+/// Emits `coverage * genome.len() / read_length` reads, rounded to the nearest
+/// whole read, using an [`Rng`] seeded with `seed`. Returns an empty `Vec`
+/// when the genome is shorter than `read_length`. The genome must be ASCII
+/// as for [`break_into_reads`]. Panics if `read_length` is 0.
+pub fn sample_reads(genome: &str, read_length: usize, coverage: f64, seed: u64) -> Vec<String> {
+    assert!(read_length >= 1, "read_length must be at least 1");
+
+    if genome.len() < read_length {
+        return Vec::new();
+    }
+
+    let count = (coverage * genome.len() as f64 / read_length as f64).round() as usize;
+    let last_start = genome.len() - read_length;
+    let mut rng = Rng::new(seed);
+
+    (0..count)
+        .map(|_| {
+            let start = rng.range_inclusive(0, last_start);
+            genome[start..start + read_length].to_string()
+        })
+        .collect()
+}
+
+/// The two mates sequenced from opposite ends of one fragment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadPair {
+    /// Forward read from the start of the fragment.
+    pub first: String,
+    /// Reverse-complemented read from the end of the fragment.
+    pub second: String,
+    /// Offset of the fragment in the genome.
+    pub fragment_start: usize,
+    /// Length of the fragment, i.e. the outer distance between the mates.
+    pub insert_size: usize,
+}
+
+/// Simulate `count` read pairs in forward-reverse (FR) orientation.
+///
+/// Each fragment length is drawn from a normal distribution with the given
+/// mean and standard deviation, rounded and clamped to between `read_length`
+/// and the genome length, and placed at a uniformly random start. The first
+/// mate is the leading `read_length` bases of the fragment on the forward
+/// strand; the second is the trailing `read_length` bases reverse
+/// complemented, so both mates point towards each other. Uses an [`Rng`]
+/// seeded with `seed`.
+///
+/// Returns an empty `Vec` when the genome is shorter than `read_length`. The
+/// genome must be ASCII as for [`break_into_reads`]. Panics if `read_length`
+/// is 0.
+pub fn simulate_pairs(
+    genome: &str,
+    read_length: usize,
+    insert_mean: f64,
+    insert_std_dev: f64,
+    count: usize,
+    seed: u64,
+) -> Vec<ReadPair> {
+    assert!(read_length >= 1, "read_length must be at least 1");
+
+    if genome.len() < read_length {
+        return Vec::new();
+    }
+
+    let mut rng = Rng::new(seed);
+
+    (0..count)
+        .map(|_| {
+            let drawn = rng.next_gaussian(insert_mean, insert_std_dev).round();
+            let insert_size = (drawn.max(0.0) as usize).clamp(read_length, genome.len());
+            let fragment_start = rng.range_inclusive(0, genome.len() - insert_size);
+            let fragment = &genome[fragment_start..fragment_start + insert_size];
+
+            ReadPair {
+                first: fragment[..read_length].to_string(),
+                second: reverse_complement(&fragment[insert_size - read_length..]),
+                fragment_start,
+                insert_size,
+            }
+        })
+        .collect()
+}
+
+/// Keep the items of `list` whose flag is `false`, in order.
+///
+/// Pairs with the flag-returning filters such as [`subsample_flags`] and
+/// [`crate::dedup::redundant_reads`], so reads and sibling lists like their
+/// qualities can be filtered by the same flags. Items past the end of
+/// `flags` are dropped.
+pub fn drop_flagged<T>(list: Vec<T>, flags: &[bool]) -> Vec<T> {
+    list.into_iter()
+        .zip(flags)
+        .filter(|(_, &flagged)| !flagged)
+        .map(|(item, _)| item)
+        .collect()
+}
+
+/// Pick reads to drop so the rest cover a genome of `genome_size` bases
+/// roughly `target_coverage` times.
+///
+/// Each read is kept independently with probability `target / current`, where
+/// the current coverage is the total read length over `genome_size`, using an
+/// [`Rng`] seeded with `seed`. Returns one flag per read, `true` where the read
+/// should be dropped, so sibling lists such as qualities can be filtered the
+/// same way. Nothing is dropped when the reads are already at or below the
+/// target, or when `genome_size` is 0.
+pub fn subsample_flags(
+    reads: &[String],
+    target_coverage: f64,
+    genome_size: usize,
+    seed: u64,
+) -> Vec<bool> {
+    let total: usize = reads.iter().map(|read| read.len()).sum();
+    let current = total as f64 / genome_size as f64;
+
+    if genome_size == 0 || current <= target_coverage {
+        return vec![false; reads.len()];
+    }
+
+    let keep = target_coverage / current;
+    let mut rng = Rng::new(seed);
+
+    reads.iter().map(|_| rng.next_f64() >= keep).collect()
+}
+
+/// Randomly thin `reads` to about `target_coverage` of a `genome_size`-base
+/// genome, keeping the survivors in their original order.
+///
+/// See [`subsample_flags`] for how reads are chosen; the same seed always
+/// keeps the same reads, and the reads come back unchanged when they are
+/// already at or below the target.
+pub fn subsample(
+    reads: Vec<String>,
+    target_coverage: f64,
+    genome_size: usize,
+    seed: u64,
+) -> Vec<String> {
+    let dropped = subsample_flags(&reads, target_coverage, genome_size, seed);
+    drop_flagged(reads, &dropped)
+}
+
+/// Follow each read with its reverse complement with probability
+/// `probability`, as if it had also been sequenced from the other strand.
+///
+/// Returns the expanded reads together with one flag per read, `true` for the
+/// added reverse-strand copies, which directly follow their forward read.
+/// A `probability` of 1 doubles every read; the choices come from `rng`.
+pub fn add_reverse_strand(
+    reads: Vec<String>,
+    probability: f64,
+    rng: &mut Rng,
+) -> (Vec<String>, Vec<bool>) {
+    let mut expanded = Vec::with_capacity(reads.len());
+    let mut reverse = Vec::with_capacity(reads.len());
+
+    for read in reads {
+        let rc = (rng.next_f64() < probability).then(|| reverse_complement(&read));
+
+        expanded.push(read);
+        reverse.push(false);
+        if let Some(rc) = rc {
+            expanded.push(rc);
+            reverse.push(true);
+        }
+    }
+
+    (expanded, reverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(reads: &[&str]) -> Vec<String> {
+        reads.iter().map(|read| read.to_string()).collect()
+    }
+
+    #[test]
+    fn stride_one_gives_every_window() {
+        assert_eq!(
+            break_into_reads("ACGTAC", 3, 1),
+            strings(&["ACG", "CGT", "GTA", "TAC"])
+        );
+    }
+
+    #[test]
+    fn stride_of_read_length_tiles_without_overlap() {
+        assert_eq!(break_into_reads("ACGTACGT", 3, 3), strings(&["ACG", "TAC"]));
+    }
+
+    #[test]
+    fn stride_of_read_length_minus_one_overlaps_by_one() {
+        let reads = break_into_reads("ACGTACGTA", 3, 2);
+
+        assert_eq!(reads, strings(&["ACG", "GTA", "ACG", "GTA"]));
+        for pair in reads.windows(2) {
+            assert_eq!(pair[0][2..], pair[1][..1]);
+        }
+    }
+
+    #[test]
+    fn genome_length_around_read_length() {
+        // len - 1 gives nothing, len gives one read, len + 1 gives two //
+        assert!(break_into_reads("ACG", 4, 1).is_empty());
+        assert_eq!(break_into_reads("ACGT", 4, 1), strings(&["ACGT"]));
+        assert_eq!(break_into_reads("ACGTA", 4, 1), strings(&["ACGT", "CGTA"]));
+        assert!(break_into_reads("", 4, 1).is_empty());
+    }
+
+    #[test]
+    fn stride_past_read_length_leaves_gaps() {
+        // Reads start at 0, 5 and 10; the bases in between are skipped //
+        assert_eq!(
+            break_into_reads("ACGTTGCAAGCCTAG", 3, 5),
+            strings(&["ACG", "GCA", "CCT"])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "step must be at least 1")]
+    fn stride_zero_panics() {
+        break_into_reads("ACGT", 2, 0);
+    }
+
+    const GENOME: &str = "ACGTTGCAGGCTAGCATCGATTGACGATCGGCTAGCATGCTAGCTAGCGG";
+
+    #[test]
+    fn variable_reads_repeat_for_the_same_seed() {
+        let reads = break_into_reads_variable(GENOME, 5, 12, 7);
+
+        assert_eq!(reads, break_into_reads_variable(GENOME, 5, 12, 7));
+        assert_ne!(reads, break_into_reads_variable(GENOME, 5, 12, 8));
+    }
+
+    #[test]
+    fn variable_read_lengths_stay_in_range() {
+        let reads = break_into_reads_variable(GENOME, 5, 12, 7);
+
+        // One read per start that still has `min_len` bases left //
+        assert_eq!(reads.len(), GENOME.len() - 5 + 1);
+        for (start, read) in reads.iter().enumerate() {
+            assert!((5..=12).contains(&read.len()), "{}", read);
+            assert!(GENOME[start..].starts_with(read.as_str()));
+        }
+    }
+
+    #[test]
+    fn sampled_read_count_matches_the_coverage() {
+        // 3x of a 50-base genome in 8-base reads is 18.75, rounded to 19 //
+        let reads = sample_reads(GENOME, 8, 3.0, 5);
+
+        assert_eq!(reads.len(), 19);
+        let bases: usize = reads.iter().map(|read| read.len()).sum();
+        let coverage = bases as f64 / GENOME.len() as f64;
+        assert!((coverage - 3.0).abs() <= 8.0 / GENOME.len() as f64);
+        assert!(reads.iter().all(|read| GENOME.contains(read.as_str())));
+    }
+
+    #[test]
+    fn sampling_is_seeded() {
+        assert_eq!(
+            sample_reads(GENOME, 8, 2.0, 5),
+            sample_reads(GENOME, 8, 2.0, 5)
+        );
+        assert_ne!(
+            sample_reads(GENOME, 8, 2.0, 5),
+            sample_reads(GENOME, 8, 2.0, 6)
+        );
+        assert!(sample_reads("ACGT", 8, 2.0, 5).is_empty());
+    }
+
+    #[test]
+    fn pairs_face_each_other_from_the_fragment_ends() {
+        let pairs = simulate_pairs(GENOME, 8, 20.0, 4.0, 30, 9);
+
+        assert_eq!(pairs.len(), 30);
+        for pair in &pairs {
+            let (start, insert) = (pair.fragment_start, pair.insert_size);
+            assert!((8..=GENOME.len()).contains(&insert));
+            assert_eq!(pair.first, GENOME[start..start + 8]);
+            assert_eq!(
+                pair.second,
+                reverse_complement(&GENOME[start + insert - 8..start + insert])
+            );
+        }
+        assert_eq!(pairs, simulate_pairs(GENOME, 8, 20.0, 4.0, 30, 9));
+    }
+
+    #[test]
+    fn insert_sizes_are_clamped_to_the_read_and_genome_length() {
+        let short = simulate_pairs(GENOME, 8, 2.0, 0.0, 5, 1);
+        let long = simulate_pairs(GENOME, 8, 500.0, 0.0, 5, 1);
+
+        assert!(short.iter().all(|pair| pair.insert_size == 8));
+        assert!(long
+            .iter()
+            .all(|pair| pair.insert_size == GENOME.len() && pair.fragment_start == 0));
+        assert!(simulate_pairs("ACGT", 8, 20.0, 4.0, 5, 1).is_empty());
+    }
+
+    #[test]
+    fn subsample_keeps_about_the_target_share() {
+        // 2000 reads of 10 bases over 1000 bases is 20x; 5x keeps a quarter //
+        let reads: Vec<String> = (0..2000).map(|i| format!("{:010}", i)).collect();
+        let kept = subsample(reads.clone(), 5.0, 1000, 3);
+
+        assert!((450..=550).contains(&kept.len()), "kept {}", kept.len());
+        // Survivors keep their original order //
+        let positions: Vec<usize> = kept
+            .iter()
+            .map(|read| reads.iter().position(|r| r == read).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(kept, subsample(reads, 5.0, 1000, 3));
+    }
+
+    #[test]
+    fn subsample_leaves_reads_already_below_target() {
+        let reads = strings(&["ACGT", "CGTA", "GTAC"]);
+
+        assert_eq!(subsample(reads.clone(), 5.0, 4, 3), reads);
+        assert_eq!(subsample(reads.clone(), 1.0, 0, 3), reads);
+    }
+
+    #[test]
+    fn drop_flagged_keeps_unflagged_items_in_order() {
+        let kept = drop_flagged(vec![1, 2, 3, 4], &[false, true, true, false]);
+        assert_eq!(kept, vec![1, 4]);
+    }
+
+    #[test]
+    fn reverse_strand_follows_every_read_at_probability_one() {
+        let forward = break_into_reads("AACGTTGCA", 4, 2);
+        let (reads, reverse) = add_reverse_strand(forward.clone(), 1.0, &mut Rng::new(0));
+
+        assert_eq!(reads.len(), 2 * forward.len());
+        for (i, window) in forward.iter().enumerate() {
+            assert_eq!(reads[2 * i], *window);
+            assert_eq!(reads[2 * i + 1], reverse_complement(window));
+            assert_eq!((reverse[2 * i], reverse[2 * i + 1]), (false, true));
+        }
+    }
+
+    #[test]
+    fn reverse_strand_adds_nothing_at_probability_zero() {
+        let forward = strings(&["ACGT", "GGCA"]);
+        let (reads, reverse) = add_reverse_strand(forward.clone(), 0.0, &mut Rng::new(0));
+
+        assert_eq!(reads, forward);
+        assert_eq!(reverse, vec![false, false]);
+    }
+}
@@ -0,0 +1,86 @@
+/// Small seeded pseudo-random generator (SplitMix64) for reproducible
+/// simulation.
+///
+/// The same seed always yields the same sequence on every platform. This is
+/// not suitable for anything security related.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform float in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform integer in `[low, high]`. Panics if `low > high`.
+    pub fn range_inclusive(&mut self, low: usize, high: usize) -> usize {
+        assert!(low <= high, "empty range {}..={}", low, high);
+        let span = ((high - low) as u64).wrapping_add(1);
+        // A span of 2^64 wraps to 0 and means every value is allowed //
+        match span {
+            0 => self.next_u64() as usize,
+            span => low + (self.next_u64() % span) as usize,
+        }
+    }
+
+    /// Normally distributed float with the given mean and standard deviation,
+    /// via the Box-Muller transform.
+    pub fn next_gaussian(&mut self, mean: f64, std_dev: f64) -> f64 {
+        // 1 - u keeps the logarithm away from zero //
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+        mean + std_dev * z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_the_same_sequence() {
+        let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+        let first: Vec<u64> = (0..8).map(|_| a.next_u64()).collect();
+
+        assert_eq!(first, (0..8).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(Rng::new(43).next_u64(), first[0]);
+    }
+
+    #[test]
+    fn range_inclusive_hits_both_bounds_and_nothing_outside() {
+        let mut rng = Rng::new(1);
+        let draws: Vec<usize> = (0..1000).map(|_| rng.range_inclusive(3, 6)).collect();
+
+        assert!(draws.iter().all(|n| (3..=6).contains(n)));
+        for n in 3..=6 {
+            assert!(draws.contains(&n), "{} never drawn", n);
+        }
+        assert_eq!(rng.range_inclusive(5, 5), 5);
+    }
+
+    #[test]
+    fn full_range_does_not_overflow() {
+        Rng::new(0).range_inclusive(0, usize::MAX);
+    }
+
+    #[test]
+    fn floats_are_in_the_unit_interval() {
+        let mut rng = Rng::new(9);
+        assert!((0..1000).all(|_| (0.0..1.0).contains(&rng.next_f64())));
+    }
+}
@@ -0,0 +1,360 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+use read_creator::genome::reverse_complement;
+use read_creator::io::read_sequences;
+
+// A fresh path under the temp directory, unique to this test process //
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("read_creator_cli_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+fn read_creator(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_read_creator"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // The binary may exit on bad arguments before reading its input //
+    let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn pipes_genome_from_stdin_to_stdout() {
+    let output = read_creator(&["4"], ">chr\nACGTAC\n");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "ACGT\nCGTA\nGTAC\n"
+    );
+    // Progress goes to stderr so it does not mix with the reads //
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Reads written to -"));
+}
+
+#[test]
+fn streams_stdin_to_stdout() {
+    let output = read_creator(&["-", "-", "3", "--stream", "--step", "2"], "ACGTACG\n");
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "ACG\nGTA\nACG\n");
+}
+
+#[test]
+fn reads_stdin_into_a_file() {
+    let path = scratch("stdin.fa");
+    let output = read_creator(&["-", path.to_str().unwrap(), "5"], "ACGTAC");
+
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        ">read_1\nACGTA\n>read_2\nCGTAC\n"
+    );
+}
+
+#[test]
+fn multi_line_input_gives_reads_without_whitespace() {
+    let genome = "ACGTTG\r\nCAGG CT\r\n\tAGCATC\n\nGATTGA\n";
+    let output = read_creator(&["4", "--step", "1"], genome);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let reads: Vec<&str> = stdout.lines().collect();
+    let joined: String = genome.split_whitespace().collect();
+    assert_eq!(reads.len(), joined.len() - 4 + 1);
+    assert!(reads
+        .iter()
+        .all(|read| read.len() == 4 && !read.contains(char::is_whitespace)));
+    assert_eq!(reads[0], "ACGT");
+    assert_eq!(*reads.last().unwrap(), "TTGA");
+}
+
+// (header, sequence) pairs of single-line FASTA text //
+fn fasta_records(text: &str) -> Vec<(String, String)> {
+    let lines: Vec<&str> = text.lines().collect();
+    lines
+        .chunks(2)
+        .map(|record| (record[0][1..].to_string(), record[1].to_string()))
+        .collect()
+}
+
+#[test]
+fn both_strands_tags_reverse_complements_of_forward_windows() {
+    let out = scratch("strands.fa");
+    let output = read_creator(
+        &[
+            "-",
+            out.to_str().unwrap(),
+            "4",
+            "--step",
+            "2",
+            "--both-strands",
+            "1",
+        ],
+        "AACGTTGCAT",
+    );
+    assert!(output.status.success());
+
+    let records = fasta_records(&fs::read_to_string(&out).unwrap());
+    let forward = ["AACG", "CGTT", "TTGC", "GCAT"];
+    assert_eq!(records.len(), 2 * forward.len());
+    for (i, window) in forward.iter().enumerate() {
+        let (fwd, rev) = (&records[2 * i], &records[2 * i + 1]);
+        assert_eq!(fwd.0, format!("read_{}", 2 * i + 1));
+        assert_eq!(fwd.1, *window);
+        assert_eq!(rev.0, format!("read_{} strand=-", 2 * i + 2));
+        assert_eq!(rev.1, reverse_complement(window));
+    }
+}
+
+#[test]
+fn both_strands_survives_low_complexity_filter() {
+    let out = scratch("strands_filtered.fa");
+    let genome = format!("ACGTTGCAGGCTAGCATCGA{}GCTAGCTAGGCTTACGATCG", "A".repeat(16));
+    let output = read_creator(
+        &[
+            "-",
+            out.to_str().unwrap(),
+            "12",
+            "--step",
+            "4",
+            "--both-strands",
+            "1",
+            "--low-complexity",
+            "filter",
+            "--complexity-window",
+            "12",
+        ],
+        &genome,
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let records = fasta_records(&fs::read_to_string(&out).unwrap());
+    assert!(records.iter().all(|(_, seq)| !seq.contains("AAAAAAAAAAAA")));
+    // Every tagged read is still the reverse complement of a kept forward read //
+    for (header, seq) in &records {
+        if header.ends_with("strand=-") {
+            let forward = reverse_complement(seq);
+            assert!(records
+                .iter()
+                .any(|(h, s)| !h.ends_with("strand=-") && *s == forward));
+        }
+    }
+}
+
+#[test]
+fn both_strands_rejects_plain_output() {
+    let out = scratch("strands.txt");
+    let output = read_creator(
+        &["-", out.to_str().unwrap(), "4", "--both-strands", "1"],
+        "ACGTAC",
+    );
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn interleaved_mates_share_a_pair_id() {
+    let out = scratch("pairs.fa");
+    let genome = "ACGTTGCAGGCTAGCATCGATTGACGATCGGCTAGCATGCTAGCTAGCGG";
+    let output = read_creator(
+        &[
+            "-",
+            out.to_str().unwrap(),
+            "10",
+            "--insert-size",
+            "30",
+            "--coverage",
+            "2",
+            "--interleaved",
+        ],
+        genome,
+    );
+    assert!(output.status.success());
+
+    let records = fasta_records(&fs::read_to_string(&out).unwrap());
+    assert_eq!(records.len(), 10);
+    for (i, pair) in records.chunks(2).enumerate() {
+        assert_eq!(pair[0].0, format!("pair_{}/1", i + 1));
+        assert_eq!(pair[1].0, format!("pair_{}/2", i + 1));
+    }
+}
+
+// Check every row of an errors TSV against the records it names //
+fn assert_errors_match(errors_tsv: &str, records: &[(String, String)]) {
+    for row in errors_tsv.lines() {
+        let fields: Vec<&str> = row.split('\t').collect();
+        let (_, seq) = records
+            .iter()
+            .find(|(header, _)| header == fields[0])
+            .unwrap_or_else(|| panic!("no record for {}", row));
+        let position: usize = fields[1].parse().unwrap();
+        assert_eq!(&seq[position..position + 1], fields[3], "{}", row);
+    }
+}
+
+#[test]
+fn errors_refer_to_reads_left_after_dedup() {
+    let (out, errors) = (scratch("dedup_errors.fa"), scratch("dedup_errors.tsv"));
+    let output = read_creator(
+        &[
+            "-",
+            out.to_str().unwrap(),
+            "8",
+            "--dedup",
+            "--error-rate",
+            "0.05",
+            "--errors",
+            errors.to_str().unwrap(),
+            "--seed",
+            "4",
+        ],
+        "ACGTACGTACGTACGTACGTACGTACGTTTGACCA",
+    );
+    assert!(output.status.success());
+
+    let records = fasta_records(&fs::read_to_string(&out).unwrap());
+    let errors = fs::read_to_string(&errors).unwrap();
+    assert!(!errors.is_empty());
+    assert_errors_match(&errors, &records);
+}
+
+#[test]
+fn errors_refer_to_mates_in_split_files() {
+    let dir = scratch("split");
+    fs::create_dir_all(&dir).unwrap();
+    let (out, errors) = (dir.join("pairs.fa"), dir.join("errors.tsv"));
+    let output = read_creator(
+        &[
+            "-",
+            out.to_str().unwrap(),
+            "10",
+            "--insert-size",
+            "30",
+            "--coverage",
+            "4",
+            "--error-rate",
+            "0.1",
+            "--errors",
+            errors.to_str().unwrap(),
+        ],
+        "ACGTTGCAGGCTAGCATCGATTGACGATCGGCTAGCATGCTAGCTAGCGG",
+    );
+    assert!(output.status.success());
+
+    let mut records = fasta_records(&fs::read_to_string(dir.join("pairs_R1.fa")).unwrap());
+    records.extend(fasta_records(
+        &fs::read_to_string(dir.join("pairs_R2.fa")).unwrap(),
+    ));
+    let errors = fs::read_to_string(&errors).unwrap();
+    assert!(errors.contains("/2\t"));
+    assert_errors_match(&errors, &records);
+}
+
+#[test]
+fn wrap_breaks_long_reads_at_the_line_width() {
+    let out = scratch("wrapped.fa");
+    let genome = "ACGTTGCAGGCTAGCATCGATTGACGATCGGCTAGCATGCTAGCTAGCGG";
+    let output = read_creator(
+        &[
+            "-",
+            out.to_str().unwrap(),
+            "20",
+            "--max-length",
+            "35",
+            "--wrap",
+            "--line-width",
+            "8",
+        ],
+        genome,
+    );
+    assert!(output.status.success());
+
+    let text = fs::read_to_string(&out).unwrap();
+    assert!(text
+        .lines()
+        .filter(|line| !line.starts_with('>'))
+        .all(|line| line.len() <= 8));
+    let records = read_sequences(out.to_str().unwrap()).unwrap();
+    assert_eq!(records.len(), genome.len() - 20 + 1);
+    for record in &records {
+        assert!((20..=35).contains(&record.seq.len()));
+        assert!(genome.contains(record.seq.as_str()));
+    }
+
+    // Plain-line output has no way to continue a sequence //
+    let output = read_creator(&["-", "-", "20", "--wrap"], genome);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn errors_without_an_error_rate_are_rejected() {
+    let errors = scratch("no_rate.tsv");
+    let output = read_creator(&["4", "--errors", errors.to_str().unwrap()], "ACGTAC");
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("--errors needs --error-rate."));
+    assert!(!errors.exists());
+}
+
+#[test]
+fn rejects_a_genome_file_with_a_unicode_character() {
+    let genome = scratch("unicode.txt");
+    fs::write(&genome, "ACGT\nAC\u{00e9}GT\n").unwrap();
+    let output = read_creator(&[genome.to_str().unwrap(), "-", "3"], "");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("invalid character '\u{00e9}' at position 6"));
+}
+
+#[test]
+fn min_qual_rejects_input_without_qualities() {
+    let out = scratch("min_qual.txt");
+    let output = read_creator(
+        &["-", out.to_str().unwrap(), "4", "--min-qual", "20"],
+        ">chr\nACGTAC\n",
+    );
+
+    // The arguments are fine on their own, so there is no usage text //
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.contains("--min-qual needs FASTQ input."));
+    assert!(!stderr.contains("Usage:"));
+}
+
+#[test]
+fn malformed_input_fails_without_usage() {
+    for input in ["@r1\nACGT\n+\nII\n", "#comment\nACGT\n"] {
+        let output = read_creator(&["4"], input);
+
+        assert_eq!(output.status.code(), Some(1), "{:?}", input);
+        assert!(!String::from_utf8(output.stderr).unwrap().contains("Usage:"));
+    }
+}
+
+#[test]
+fn bad_arguments_print_usage() {
+    let output = read_creator(&["4", "--step", "0"], "ACGT");
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr).unwrap().contains("Usage:"));
+}